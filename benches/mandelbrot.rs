@@ -42,7 +42,7 @@ fn simd4(c_x: f32x4, c_y: f32x4, max_iter: u32) -> u32x4 {
         let mask = sum.lt(f32x4::splat(4.0));
 
         if !mask.any() { break }
-        count = count + mask.to_i().select(u32x4::splat(1), u32x4::splat(0));
+        count = count.add_masked(mask, u32x4::splat(1));
 
         x = xx - yy + c_x;
         y = xy + xy + c_y;
@@ -64,7 +64,7 @@ fn simd8(c_x: f32x8, c_y: f32x8, max_iter: u32) -> u32x8 {
         let mask = sum.lt(f32x8::splat(4.0));
 
         if !mask.any() { break }
-        count = count + mask.to_i().select(u32x8::splat(1), u32x8::splat(0));
+        count = count.add_masked(mask, u32x8::splat(1));
 
         x = xx - yy + c_x;
         y = xy + xy + c_y;
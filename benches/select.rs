@@ -0,0 +1,135 @@
+#![feature(test)]
+#![feature(cfg_target_feature)]
+extern crate test;
+extern crate simd;
+
+use test::black_box as bb;
+use test::Bencher as B;
+use simd::{f32x4, bool32ix4};
+#[cfg(target_feature = "avx")]
+use simd::x86::avx::{f32x8, bool32ix8};
+
+const N: usize = 1024;
+
+fn data() -> Vec<f32> {
+    (0..N).map(|i| (i as f32 * 0.37).sin()).collect()
+}
+
+// `select` as it was before it used the native blends.
+#[inline]
+fn bitwise_select(mask: bool32ix4, then: f32x4, else_: f32x4) -> f32x4 {
+    let mask = mask.to_repr().to_u32();
+    f32x4::from_bits((then.to_bits() & mask) | (else_.to_bits() & !mask))
+}
+#[cfg(target_feature = "avx")]
+#[inline]
+fn bitwise_select8(mask: bool32ix8, then: f32x8, else_: f32x8) -> f32x8 {
+    let mask = mask.to_repr().to_u32();
+    f32x8::from_bits((then.to_bits() & mask) | (else_.to_bits() & !mask))
+}
+
+// leaky ReLU: scale the negative lanes down.
+#[bench]
+fn select_bitwise(b: &mut B) {
+    let x = data();
+    let mut out = vec![0.0; N];
+    b.iter(|| {
+        let x = bb(&x);
+        let zero = f32x4::splat(0.0);
+        let leak = f32x4::splat(0.01);
+        let mut i = 0;
+        while i < N {
+            let v = f32x4::load(x, i);
+            bitwise_select(v.lt(zero), v * leak, v).store(&mut out, i);
+            i += 4;
+        }
+        bb(&out);
+    })
+}
+#[bench]
+fn select_native(b: &mut B) {
+    let x = data();
+    let mut out = vec![0.0; N];
+    b.iter(|| {
+        let x = bb(&x);
+        let zero = f32x4::splat(0.0);
+        let leak = f32x4::splat(0.01);
+        let mut i = 0;
+        while i < N {
+            let v = f32x4::load(x, i);
+            v.lt(zero).select(v * leak, v).store(&mut out, i);
+            i += 4;
+        }
+        bb(&out);
+    })
+}
+
+#[cfg(target_feature = "avx")]
+#[bench]
+fn select_bitwise_8(b: &mut B) {
+    let x = data();
+    let mut out = vec![0.0; N];
+    b.iter(|| {
+        let x = bb(&x);
+        let zero = f32x8::splat(0.0);
+        let leak = f32x8::splat(0.01);
+        let mut i = 0;
+        while i < N {
+            let v = f32x8::load(x, i);
+            bitwise_select8(v.lt(zero), v * leak, v).store(&mut out, i);
+            i += 8;
+        }
+        bb(&out);
+    })
+}
+#[cfg(target_feature = "avx")]
+#[bench]
+fn select_native_8(b: &mut B) {
+    let x = data();
+    let mut out = vec![0.0; N];
+    b.iter(|| {
+        let x = bb(&x);
+        let zero = f32x8::splat(0.0);
+        let leak = f32x8::splat(0.01);
+        let mut i = 0;
+        while i < N {
+            let v = f32x8::load(x, i);
+            v.lt(zero).select(v * leak, v).store(&mut out, i);
+            i += 8;
+        }
+        bb(&out);
+    })
+}
+
+// interleave two streams, alternating lanes.
+#[bench]
+fn blend_bitwise(b: &mut B) {
+    let x = data();
+    let mut out = vec![0.0; N];
+    b.iter(|| {
+        let x = bb(&x);
+        let mask = bool32ix4::new(false, true, false, true);
+        let mut i = 0;
+        while i < N {
+            let v = f32x4::load(x, i);
+            bitwise_select(mask, v + v, v).store(&mut out, i);
+            i += 4;
+        }
+        bb(&out);
+    })
+}
+#[bench]
+fn blend_native(b: &mut B) {
+    let x = data();
+    let mut out = vec![0.0; N];
+    b.iter(|| {
+        let x = bb(&x);
+        let mut i = 0;
+        while i < N {
+            let v = f32x4::load(x, i);
+            v.blend_bitmask(v + v, 0b1010).store(&mut out, i);
+            i += 4;
+        }
+        bb(&out);
+    })
+}
@@ -0,0 +1,40 @@
+#![feature(test)]
+extern crate test;
+extern crate simd;
+
+use test::black_box as bb;
+use test::Bencher as B;
+use simd::{f32x4, SimdSum};
+
+const N: usize = 1027;
+
+fn data() -> Vec<f32> {
+    (0..N).map(|i| (i as f32 * 0.37).sin()).collect()
+}
+
+#[bench]
+fn sum_scalar(b: &mut B) {
+    let x = data();
+    b.iter(|| bb(&x).iter().sum::<f32>())
+}
+
+#[bench]
+fn sum_simd_sum(b: &mut B) {
+    let x = data();
+    b.iter(|| bb(&x[..]).simd_sum())
+}
+
+#[bench]
+fn sum_vectors(b: &mut B) {
+    let x = data();
+    b.iter(|| {
+        let x = bb(&x);
+        (0..x.len() / 4).map(|i| f32x4::load(x, 4 * i)).sum::<f32x4>()
+    })
+}
+
+#[bench]
+fn sum_i32_simd_sum(b: &mut B) {
+    let x: Vec<i32> = (0..N as i32).collect();
+    b.iter(|| bb(&x[..]).simd_sum())
+}
@@ -70,10 +70,9 @@ fn advance(bodies: &mut [Body; N_BODIES], dt: f64) {
         dsquared = dx[0] * dx[0] + dx[1] * dx[1] + dx[2] * dx[2];
         distance = dsquared.to_f32().approx_rsqrt().to_f64();
         for _ in 0..2 {
-            distance = distance * f64x2::splat(1.5) -
-                ((f64x2::splat(0.5) * dsquared) * distance) * (distance * distance)
+            distance = distance * 1.5 - ((0.5 * dsquared) * distance) * (distance * distance)
         }
-        dmag = f64x2::splat(dt) / dsquared * distance;
+        dmag = dt / dsquared * distance;
         dmag.store(&mut mag, i);
 
         i += 2;
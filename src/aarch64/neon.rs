@@ -1,7 +1,8 @@
 use super::super::*;
 use {simd_cast, f32x2};
 
-pub use sixty_four::{f64x2, i64x2, u64x2, bool64ix2, bool64fx2};
+#[allow(deprecated)]
+pub use sixty_four::{f64x2, i64x2, u64x2, bool64ix2, bool64fx2, bool64i, bool64f};
 #[repr(simd)]
 #[derive(Copy, Clone)]
 pub struct u32x2(u32, u32);
@@ -94,10 +95,6 @@ extern "platform-intrinsic" {
     fn aarch64_vfmulx_f64(x: f64x1, y: f64x1) -> f64x1;
     fn aarch64_vfmulxq_f32(x: f32x4, y: f32x4) -> f32x4;
     fn aarch64_vfmulxq_f64(x: f64x2, y: f64x2) -> f64x2;
-    fn aarch64_vfma_f32(x: f32x2, y: f32x2) -> f32x2;
-    fn aarch64_vfma_f64(x: f64x1, y: f64x1) -> f64x1;
-    fn aarch64_vfmaq_f32(x: f32x4, y: f32x4) -> f32x4;
-    fn aarch64_vfmaq_f64(x: f64x2, y: f64x2) -> f64x2;
     fn aarch64_vqdmulh_s16(x: i16x4, y: i16x4) -> i16x4;
     fn aarch64_vqdmulh_s32(x: i32x2, y: i32x2) -> i32x2;
     fn aarch64_vqdmulhq_s16(x: i16x8, y: i16x8) -> i16x8;
@@ -596,6 +593,35 @@ extern "platform-intrinsic" {
     fn aarch64_vqtbx4q_u8(x: u8x16, y: (u8x16, u8x16, u8x16, u8x16), z: u8x16) -> u8x16;
 }
 
+// `rbit` on vectors isn't a platform intrinsic, and the `vfma` ones
+// only take two operands.
+#[allow(dead_code, improper_ctypes)]
+extern {
+    #[link_name = "llvm.bitreverse.v16i8"]
+    fn aarch64_vrbitq_u8(x: u8x16) -> u8x16;
+    #[link_name = "llvm.fma.v2f32"]
+    fn aarch64_vfma_f32(x: f32x2, y: f32x2, z: f32x2) -> f32x2;
+    #[link_name = "llvm.fma.v1f64"]
+    fn aarch64_vfma_f64(x: f64x1, y: f64x1, z: f64x1) -> f64x1;
+    #[link_name = "llvm.fma.v4f32"]
+    fn aarch64_vfmaq_f32(x: f32x4, y: f32x4, z: f32x4) -> f32x4;
+    #[link_name = "llvm.fma.v2f64"]
+    fn aarch64_vfmaq_f64(x: f64x2, y: f64x2, z: f64x2) -> f64x2;
+}
+
+// Nor are there platform intrinsics for `vminnm` and `vmaxnm`.
+#[allow(dead_code, improper_ctypes)]
+extern {
+    #[link_name = "llvm.aarch64.neon.fminnm.v4f32"]
+    fn aarch64_vminnmq_f32(x: f32x4, y: f32x4) -> f32x4;
+    #[link_name = "llvm.aarch64.neon.fminnm.v2f64"]
+    fn aarch64_vminnmq_f64(x: f64x2, y: f64x2) -> f64x2;
+    #[link_name = "llvm.aarch64.neon.fmaxnm.v4f32"]
+    fn aarch64_vmaxnmq_f32(x: f32x4, y: f32x4) -> f32x4;
+    #[link_name = "llvm.aarch64.neon.fmaxnm.v2f64"]
+    fn aarch64_vmaxnmq_f64(x: f64x2, y: f64x2) -> f64x2;
+}
+
 pub trait Aarch64F32x4 {
     fn to_f64(self) -> f64x2;
 }
@@ -630,12 +656,18 @@ impl Aarch64I8x16 for i8x16 {
 #[doc(hidden)]
 pub mod common {
     use super::super::super::*;
+    use super::{u64x2, f64x2};
+    use {bitcast, simd_shuffle16};
     use std::mem;
 
     #[inline]
     pub fn f32x4_sqrt(x: f32x4) -> f32x4 {
         unsafe {super::aarch64_vsqrtq_f32(x)}
     }
+    // the `vrecpe` and `vrsqrte` estimates are good to a little under
+    // 8 bits.
+    pub const F32X4_APPROX_BITS: u32 = 7;
+
     #[inline]
     pub fn f32x4_approx_rsqrt(x: f32x4) -> f32x4 {
         unsafe {super::aarch64_vrsqrteq_f32(x)}
@@ -644,13 +676,47 @@ pub mod common {
     pub fn f32x4_approx_reciprocal(x: f32x4) -> f32x4 {
         unsafe {super::aarch64_vrecpeq_f32(x)}
     }
+    // `fminnm` and `fmaxnm` are IEEE 754's `minNum` and `maxNum`, and
+    // order `-0.0` below `+0.0`, like `min_num` and `max_num`.
+    #[inline]
+    pub fn f32x4_min_num(x: f32x4, y: f32x4) -> f32x4 {
+        unsafe {super::aarch64_vminnmq_f32(x, y)}
+    }
     #[inline]
-    pub fn f32x4_max(x: f32x4, y: f32x4) -> f32x4 {
-        unsafe {super::aarch64_vmaxq_f32(x, y)}
+    pub fn f32x4_max_num(x: f32x4, y: f32x4) -> f32x4 {
+        unsafe {super::aarch64_vmaxnmq_f32(x, y)}
     }
     #[inline]
-    pub fn f32x4_min(x: f32x4, y: f32x4) -> f32x4 {
-        unsafe {super::aarch64_vminq_f32(x, y)}
+    pub fn f64x2_min_num(x: f64x2, y: f64x2) -> f64x2 {
+        unsafe {super::aarch64_vminnmq_f64(x, y)}
+    }
+    #[inline]
+    pub fn f64x2_max_num(x: f64x2, y: f64x2) -> f64x2 {
+        unsafe {super::aarch64_vmaxnmq_f64(x, y)}
+    }
+
+    macro_rules! fmas {
+        ($($ty: ident ($fma: ident): $mul_add: ident, $mul_sub: ident, $neg_mul_add: ident;)*) => {
+            $(
+                #[inline]
+                pub fn $mul_add(x: $ty, y: $ty, z: $ty) -> $ty {
+                    unsafe {super::$fma(x, y, z)}
+                }
+                #[inline]
+                pub fn $mul_sub(x: $ty, y: $ty, z: $ty) -> $ty {
+                    unsafe {super::$fma(x, y, -z)}
+                }
+                #[inline]
+                pub fn $neg_mul_add(x: $ty, y: $ty, z: $ty) -> $ty {
+                    unsafe {super::$fma(-x, y, z)}
+                }
+                )*
+        }
+    }
+
+    fmas! {
+        f32x4 (aarch64_vfmaq_f32): f32x4_mul_add, f32x4_mul_sub, f32x4_neg_mul_add;
+        f64x2 (aarch64_vfmaq_f64): f64x2_mul_add, f64x2_mul_sub, f64x2_neg_mul_add;
     }
 
     macro_rules! bools {
@@ -673,9 +739,51 @@ pub mod common {
     }
 
     bools! {
-        bool32fx4, bool32fx4_all(aarch64_vminvq_u32), bool32fx4_any(aarch64_vmaxvq_u32);
         bool8ix16, bool8ix16_all(aarch64_vminvq_u8), bool8ix16_any(aarch64_vmaxvq_u8);
         bool16ix8, bool16ix8_all(aarch64_vminvq_u16), bool16ix8_any(aarch64_vmaxvq_u16);
         bool32ix4, bool32ix4_all(aarch64_vminvq_u32), bool32ix4_any(aarch64_vmaxvq_u32);
     }
+
+    // keep bit `i` of lane `i`, and then add across the vector.
+    #[inline]
+    pub fn bool32ix4_bitmask(x: bool32ix4) -> u64 {
+        unsafe {
+            let bits = u32x4::new(1, 2, 4, 8);
+            super::aarch64_vaddvq_u32(mem::transmute::<_, u32x4>(x) & bits) as u64
+        }
+    }
+    #[inline]
+    pub fn bool16ix8_bitmask(x: bool16ix8) -> u64 {
+        unsafe {
+            let bits = u16x8::new(1, 2, 4, 8, 16, 32, 64, 128);
+            super::aarch64_vaddvq_u16(mem::transmute::<_, u16x8>(x) & bits) as u64
+        }
+    }
+    #[inline]
+    pub fn bool8ix16_bitmask(x: bool8ix16) -> u64 {
+        unsafe {
+            let bits = u8x16::new(1, 2, 4, 8, 16, 32, 64, 128,
+                                  1, 2, 4, 8, 16, 32, 64, 128);
+            let x = mem::transmute::<_, u8x16>(x) & bits;
+            let (lo, hi): (super::u8x8, super::u8x8) = mem::transmute(x);
+            super::aarch64_vaddv_u8(lo) as u64 | (super::aarch64_vaddv_u8(hi) as u64) << 8
+        }
+    }
+
+    // LLVM turns these into `bsl`.
+    bitwise_selects! {
+        bool8ix16: bool8ix16_select(i8x16);
+        bool16ix8: bool16ix8_select(i16x8);
+        bool32ix4: bool32ix4_select(i32x4);
+    }
+
+    byte_shuffle_swaps! {
+        u16x8_swap_bytes(u16x8) [1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14];
+        u32x4_swap_bytes(u32x4) [3, 2, 1, 0, 7, 6, 5, 4, 11, 10, 9, 8, 15, 14, 13, 12];
+        u64x2_swap_bytes(u64x2) [7, 6, 5, 4, 3, 2, 1, 0, 15, 14, 13, 12, 11, 10, 9, 8];
+    }
+    #[inline]
+    pub fn u8x16_reverse_bits(x: u8x16) -> u8x16 {
+        unsafe {super::aarch64_vrbitq_u8(x)}
+    }
 }
@@ -81,8 +81,6 @@ extern "platform-intrinsic" {
     fn arm_vraddhn_u32(x: u32x4, y: u32x4) -> u16x4;
     fn arm_vraddhn_s64(x: i64x2, y: i64x2) -> i32x2;
     fn arm_vraddhn_u64(x: u64x2, y: u64x2) -> u32x2;
-    fn arm_vfma_f32(x: f32x2, y: f32x2) -> f32x2;
-    fn arm_vfmaq_f32(x: f32x4, y: f32x4) -> f32x4;
     fn arm_vqdmulh_s16(x: i16x4, y: i16x4) -> i16x4;
     fn arm_vqdmulh_s32(x: i32x2, y: i32x2) -> i32x2;
     fn arm_vqdmulhq_s16(x: i16x8, y: i16x8) -> i16x8;
@@ -449,6 +447,17 @@ extern "platform-intrinsic" {
     fn arm_vtbx4_u8(x: u8x8, y: (u8x8, u8x8, u8x8, u8x8), z: u8x8) -> u8x8;
 }
 
+// the `vfma` platform intrinsics only take two operands, so these go
+// to LLVM's `fma` directly (which is `vfma` with VFPv4, and a
+// correctly rounded library call without).
+#[allow(dead_code, improper_ctypes)]
+extern {
+    #[link_name = "llvm.fma.v2f32"]
+    fn arm_vfma_f32(x: f32x2, y: f32x2, z: f32x2) -> f32x2;
+    #[link_name = "llvm.fma.v4f32"]
+    fn arm_vfmaq_f32(x: f32x4, y: f32x4, z: f32x4) -> f32x4;
+}
+
 
 impl u8x8 {
     #[inline]
@@ -473,12 +482,18 @@ impl u8x8 {
 pub mod common {
     use super::super::super::*;
     use super::*;
+    use sixty_four::u64x2;
+    use {bitcast, simd_shuffle16};
     use std::mem;
 
     #[inline]
     pub fn f32x4_sqrt(x: f32x4) -> f32x4 {
         unsafe {super::arm_vsqrtq_f32(x)}
     }
+    // the `vrecpe` and `vrsqrte` estimates are good to a little under
+    // 8 bits.
+    pub const F32X4_APPROX_BITS: u32 = 7;
+
     #[inline]
     pub fn f32x4_approx_rsqrt(x: f32x4) -> f32x4 {
         unsafe {super::arm_vrsqrteq_f32(x)}
@@ -487,13 +502,28 @@ pub mod common {
     pub fn f32x4_approx_reciprocal(x: f32x4) -> f32x4 {
         unsafe {super::arm_vrecpeq_f32(x)}
     }
+    // `vminq_f32` and `vmaxq_f32` return NaN if either lane is NaN,
+    // and ARMv7 has no `vminnm`.
     #[inline]
-    pub fn f32x4_max(x: f32x4, y: f32x4) -> f32x4 {
-        unsafe {super::arm_vmaxq_f32(x, y)}
+    pub fn f32x4_min_num(x: f32x4, y: f32x4) -> f32x4 {
+        min_num_lanes!(x, y)
     }
     #[inline]
-    pub fn f32x4_min(x: f32x4, y: f32x4) -> f32x4 {
-        unsafe {super::arm_vminq_f32(x, y)}
+    pub fn f32x4_max_num(x: f32x4, y: f32x4) -> f32x4 {
+        max_num_lanes!(x, y)
+    }
+
+    #[inline]
+    pub fn f32x4_mul_add(x: f32x4, y: f32x4, z: f32x4) -> f32x4 {
+        unsafe {super::arm_vfmaq_f32(x, y, z)}
+    }
+    #[inline]
+    pub fn f32x4_mul_sub(x: f32x4, y: f32x4, z: f32x4) -> f32x4 {
+        unsafe {super::arm_vfmaq_f32(x, y, -z)}
+    }
+    #[inline]
+    pub fn f32x4_neg_mul_add(x: f32x4, y: f32x4, z: f32x4) -> f32x4 {
+        unsafe {super::arm_vfmaq_f32(-x, y, z)}
     }
 
     macro_rules! bools {
@@ -522,9 +552,62 @@ pub mod common {
     }
 
     bools! {
-        bool32fx4, arm::neon::u32x2, bool32fx4_all(arm_vpmin_u32), bool32fx4_any(arm_vpmax_u32);
         bool8ix16, arm::neon::u8x8, bool8ix16_all(arm_vpmin_u8), bool8ix16_any(arm_vpmax_u8);
         bool16ix8, arm::neon::u16x4, bool16ix8_all(arm_vpmin_u16), bool16ix8_any(arm_vpmax_u16);
         bool32ix4, arm::neon::u32x2, bool32ix4_all(arm_vpmin_u32), bool32ix4_any(arm_vpmax_u32);
     }
+
+    // keep bit `i` of lane `i`, and then add pairwise until the
+    // halves are reduced to one lane each.
+    #[inline]
+    pub fn bool32ix4_bitmask(x: bool32ix4) -> u64 {
+        unsafe {
+            let bits = u32x4::new(1, 2, 4, 8);
+            let x = mem::transmute::<_, u32x4>(x) & bits;
+            let (lo, hi): (u32x2, u32x2) = mem::transmute(x);
+            let x = super::arm_vpadd_u32(lo, hi);
+            (x.0 | x.1) as u64
+        }
+    }
+    #[inline]
+    pub fn bool16ix8_bitmask(x: bool16ix8) -> u64 {
+        unsafe {
+            let bits = u16x8::new(1, 2, 4, 8, 16, 32, 64, 128);
+            let x = mem::transmute::<_, u16x8>(x) & bits;
+            let (lo, hi): (u16x4, u16x4) = mem::transmute(x);
+            let x = super::arm_vpadd_u16(lo, hi);
+            let x = super::arm_vpadd_u16(x, x);
+            (x.0 | x.1) as u64
+        }
+    }
+    #[inline]
+    pub fn bool8ix16_bitmask(x: bool8ix16) -> u64 {
+        unsafe {
+            let bits = u8x16::new(1, 2, 4, 8, 16, 32, 64, 128,
+                                  1, 2, 4, 8, 16, 32, 64, 128);
+            let x = mem::transmute::<_, u8x16>(x) & bits;
+            let (lo, hi): (u8x8, u8x8) = mem::transmute(x);
+            let x = super::arm_vpadd_u8(lo, hi);
+            let x = super::arm_vpadd_u8(x, x);
+            let x = super::arm_vpadd_u8(x, x);
+            x.0 as u64 | (x.1 as u64) << 8
+        }
+    }
+
+    // LLVM turns these into `bsl`.
+    bitwise_selects! {
+        bool8ix16: bool8ix16_select(i8x16);
+        bool16ix8: bool16ix8_select(i16x8);
+        bool32ix4: bool32ix4_select(i32x4);
+    }
+
+    byte_shuffle_swaps! {
+        u16x8_swap_bytes(u16x8) [1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14];
+        u32x4_swap_bytes(u32x4) [3, 2, 1, 0, 7, 6, 5, 4, 11, 10, 9, 8, 15, 14, 13, 12];
+        u64x2_swap_bytes(u64x2) [7, 6, 5, 4, 3, 2, 1, 0, 15, 14, 13, 12, 11, 10, 9, 8];
+    }
+    // ARMv7 has no `vrbit` on vectors.
+    bit_group_swaps! {
+        u8x16_reverse_bits(u8x16) [1 => 0x55, 2 => 0x33, 4 => 0x0f];
+    }
 }
@@ -0,0 +1,147 @@
+use super::*;
+use sixty_four::{u64x2, i64x2, f64x2, bool64ix2};
+use v256::{u8x32, i8x32, u16x16, i16x16, u32x8, i32x8, u64x4, i64x4, f32x8, f64x4,
+           bool8ix32, bool16ix16, bool32ix8, bool64ix4};
+use std::mem;
+
+/// Reinterpret the bits of a vector as another vector of the same
+/// size.
+///
+/// This is implemented for every pair of 128-bit vectors and every
+/// pair of 256-bit vectors, so the sizes always match and the
+/// conversion is free. Boolean vectors can be reinterpreted as
+/// other vectors, but not the reverse, since not every bit pattern
+/// is a valid boolean vector.
+///
+/// The floating point vectors also have inherent `from_bits` methods
+/// that take the unsigned vector with the same lanes, which are
+/// found first by `f32x4::from_bits(x)`; use `x.into_bits()` or
+/// `FromBits::from_bits(x)` for the other types.
+pub trait FromBits<T>: Sized {
+    /// Reinterpret the bits of `x` as `Self`.
+    fn from_bits(x: T) -> Self;
+}
+
+/// Reinterpret the bits of a vector as another vector of the same
+/// size.
+///
+/// This is the counterpart of `FromBits`, like `Into` is of `From`,
+/// and is implemented for every pair that `FromBits` is, e.g.
+///
+/// ```rust,ignore
+/// let bytes: u8x16 = f32x4::splat(1.0).into_bits();
+/// ```
+pub trait IntoBits<T>: Sized {
+    /// Reinterpret the bits of `self` as `T`.
+    fn into_bits(self) -> T;
+}
+
+impl<T, U: FromBits<T>> IntoBits<U> for T {
+    #[inline]
+    fn into_bits(self) -> U {
+        U::from_bits(self)
+    }
+}
+
+macro_rules! from_bits_impls {
+    ($($to: ident: $($from: ident),*;)*) => {
+        $($(impl FromBits<$from> for $to {
+            #[inline]
+            fn from_bits(x: $from) -> $to {
+                // `transmute` won't compile if the sizes differ
+                unsafe {mem::transmute(x)}
+            }
+        })*)*
+    }
+}
+
+from_bits_impls! {
+    u8x16: i8x16, u16x8, i16x8, u32x4, i32x4, f32x4, u64x2, i64x2, f64x2,
+        bool8ix16, bool16ix8, bool32ix4, bool64ix2;
+    i8x16: u8x16, u16x8, i16x8, u32x4, i32x4, f32x4, u64x2, i64x2, f64x2,
+        bool8ix16, bool16ix8, bool32ix4, bool64ix2;
+    u16x8: u8x16, i8x16, i16x8, u32x4, i32x4, f32x4, u64x2, i64x2, f64x2,
+        bool8ix16, bool16ix8, bool32ix4, bool64ix2;
+    i16x8: u8x16, i8x16, u16x8, u32x4, i32x4, f32x4, u64x2, i64x2, f64x2,
+        bool8ix16, bool16ix8, bool32ix4, bool64ix2;
+    u32x4: u8x16, i8x16, u16x8, i16x8, i32x4, f32x4, u64x2, i64x2, f64x2,
+        bool8ix16, bool16ix8, bool32ix4, bool64ix2;
+    i32x4: u8x16, i8x16, u16x8, i16x8, u32x4, f32x4, u64x2, i64x2, f64x2,
+        bool8ix16, bool16ix8, bool32ix4, bool64ix2;
+    f32x4: u8x16, i8x16, u16x8, i16x8, u32x4, i32x4, u64x2, i64x2, f64x2,
+        bool8ix16, bool16ix8, bool32ix4, bool64ix2;
+    u64x2: u8x16, i8x16, u16x8, i16x8, u32x4, i32x4, f32x4, i64x2, f64x2,
+        bool8ix16, bool16ix8, bool32ix4, bool64ix2;
+    i64x2: u8x16, i8x16, u16x8, i16x8, u32x4, i32x4, f32x4, u64x2, f64x2,
+        bool8ix16, bool16ix8, bool32ix4, bool64ix2;
+    f64x2: u8x16, i8x16, u16x8, i16x8, u32x4, i32x4, f32x4, u64x2, i64x2,
+        bool8ix16, bool16ix8, bool32ix4, bool64ix2;
+
+    u8x32: i8x32, u16x16, i16x16, u32x8, i32x8, f32x8, u64x4, i64x4, f64x4,
+        bool8ix32, bool16ix16, bool32ix8, bool64ix4;
+    i8x32: u8x32, u16x16, i16x16, u32x8, i32x8, f32x8, u64x4, i64x4, f64x4,
+        bool8ix32, bool16ix16, bool32ix8, bool64ix4;
+    u16x16: u8x32, i8x32, i16x16, u32x8, i32x8, f32x8, u64x4, i64x4, f64x4,
+        bool8ix32, bool16ix16, bool32ix8, bool64ix4;
+    i16x16: u8x32, i8x32, u16x16, u32x8, i32x8, f32x8, u64x4, i64x4, f64x4,
+        bool8ix32, bool16ix16, bool32ix8, bool64ix4;
+    u32x8: u8x32, i8x32, u16x16, i16x16, i32x8, f32x8, u64x4, i64x4, f64x4,
+        bool8ix32, bool16ix16, bool32ix8, bool64ix4;
+    i32x8: u8x32, i8x32, u16x16, i16x16, u32x8, f32x8, u64x4, i64x4, f64x4,
+        bool8ix32, bool16ix16, bool32ix8, bool64ix4;
+    f32x8: u8x32, i8x32, u16x16, i16x16, u32x8, i32x8, u64x4, i64x4, f64x4,
+        bool8ix32, bool16ix16, bool32ix8, bool64ix4;
+    u64x4: u8x32, i8x32, u16x16, i16x16, u32x8, i32x8, f32x8, i64x4, f64x4,
+        bool8ix32, bool16ix16, bool32ix8, bool64ix4;
+    i64x4: u8x32, i8x32, u16x16, i16x16, u32x8, i32x8, f32x8, u64x4, f64x4,
+        bool8ix32, bool16ix16, bool32ix8, bool64ix4;
+    f64x4: u8x32, i8x32, u16x16, i16x16, u32x8, i32x8, f32x8, u64x4, i64x4,
+        bool8ix32, bool16ix16, bool32ix8, bool64ix4;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_bytes() {
+        let x = f32x4::new(1.0, -2.0, 0.5, -0.0);
+        let bytes: u8x16 = x.into_bits();
+        let mut want = [0u8; 16];
+        for i in 0..4 {
+            let lane: [u8; 4] = unsafe { mem::transmute(x.extract(i)) };
+            want[4 * i as usize..4 * i as usize + 4].copy_from_slice(&lane);
+        }
+        assert_eq!(bytes, u8x16::load(&want, 0));
+
+        // and back, through any other type
+        let y: f32x4 = FromBits::from_bits(bytes);
+        assert_eq!(y.to_bits(), x.to_bits());
+        let words: i16x8 = bytes.into_bits();
+        let y: f32x4 = words.into_bits();
+        assert_eq!(y.to_bits(), x.to_bits());
+        let y: f32x4 = IntoBits::<f64x2>::into_bits(x).into_bits();
+        assert_eq!(y.to_bits(), x.to_bits());
+    }
+
+    #[test]
+    fn masks() {
+        // true lanes are all ones
+        let m = bool32ix4::new(true, false, true, false);
+        let x: u32x4 = m.into_bits();
+        assert_eq!(x, u32x4::new(!0, 0, !0, 0));
+        let x: u64x2 = m.into_bits();
+        assert_eq!(x, u64x2::new(0xffffffff, 0xffffffff));
+        let x: i8x32 = bool64ix4::new(false, true, false, false).into_bits();
+        assert_eq!(x.ne(i8x32::splat(0)).to_bitmask(), 0xff00);
+    }
+
+    #[test]
+    fn wide() {
+        let x = u32x8::new(1, 2, 3, 4, 5, 6, 7, 0x80000000);
+        let y: i64x4 = x.into_bits();
+        assert_eq!(y, i64x4::new(2 << 32 | 1, 4 << 32 | 3, 6 << 32 | 5, -0x80000000 << 32 | 7));
+        let z: u32x8 = FromBits::from_bits(y);
+        assert_eq!(z, x);
+    }
+}
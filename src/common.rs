@@ -7,18 +7,120 @@ use super::{
     simd_cast,
     simd_add, simd_sub, simd_mul, simd_div, simd_shl, simd_shr, simd_and, simd_or, simd_xor,
 
-    Unalign, bitcast,
+    Unalign, bitcast, fmt_lanes, parse_lanes,
 };
+use v256::{u16x16, i16x16, u32x8, i32x8, u64x4, i64x4};
 use std::mem;
 use std::ops;
 
 #[cfg(any(target_arch = "x86",
           target_arch = "x86_64"))]
-use x86::sse2::common;
+use x86::sse2::common as platform;
 #[cfg(any(target_arch = "arm"))]
-use arm::neon::common;
+use arm::neon::common as platform;
 #[cfg(any(target_arch = "aarch64"))]
-use aarch64::neon::common;
+use aarch64::neon::common as platform;
+
+#[cfg(not(feature = "deterministic"))]
+use self::platform as common;
+// The estimate instructions differ between platforms, so with
+// `deterministic` they're replaced by divisions, which are correctly
+// rounded everywhere.
+#[cfg(feature = "deterministic")]
+mod common {
+    pub use super::platform::*;
+    use super::{f32x4, platform};
+
+    pub const F32X4_APPROX_BITS: u32 = 23;
+
+    #[inline]
+    pub fn f32x4_approx_rsqrt(x: f32x4) -> f32x4 {
+        f32x4::splat(1.0) / platform::f32x4_sqrt(x)
+    }
+    #[inline]
+    pub fn f32x4_approx_reciprocal(x: f32x4) -> f32x4 {
+        f32x4::splat(1.0) / x
+    }
+}
+
+#[cfg(all(any(target_arch = "x86",
+              target_arch = "x86_64"),
+          target_feature = "fma"))]
+pub use x86::fma::common as fma;
+#[cfg(not(all(any(target_arch = "x86",
+                  target_arch = "x86_64"),
+              target_feature = "fma")))]
+#[doc(hidden)]
+pub mod fma {
+    use super::*;
+    use sixty_four::f64x2;
+    use v256::{f64x4, f32x8, LowHigh128};
+    use {simd_shuffle4, simd_shuffle8};
+
+    #[cfg(target_arch = "arm")]
+    pub use arm::neon::common::{f32x4_mul_add, f32x4_mul_sub, f32x4_neg_mul_add};
+    #[cfg(target_arch = "aarch64")]
+    pub use aarch64::neon::common::{f32x4_mul_add, f32x4_mul_sub, f32x4_neg_mul_add,
+                                    f64x2_mul_add, f64x2_mul_sub, f64x2_neg_mul_add};
+
+    // the scalar `mul_add` is correctly rounded (via a software
+    // routine if need be), so this is precise but slow.
+    macro_rules! fmas {
+        ($($ty: ident ($length: expr): $mul_add: ident, $mul_sub: ident, $neg_mul_add: ident;)*) => {
+            $(
+                #[inline]
+                pub fn $mul_add(x: $ty, y: $ty, z: $ty) -> $ty {
+                    let mut ret = x;
+                    for i in 0..$length {
+                        ret = ret.replace(i, x.extract(i).mul_add(y.extract(i), z.extract(i)));
+                    }
+                    ret
+                }
+                #[inline]
+                pub fn $mul_sub(x: $ty, y: $ty, z: $ty) -> $ty {
+                    $mul_add(x, y, -z)
+                }
+                #[inline]
+                pub fn $neg_mul_add(x: $ty, y: $ty, z: $ty) -> $ty {
+                    $mul_add(-x, y, z)
+                }
+                )*
+        }
+    }
+
+    #[cfg(not(any(target_arch = "arm", target_arch = "aarch64")))]
+    fmas! {
+        f32x4 (4): f32x4_mul_add, f32x4_mul_sub, f32x4_neg_mul_add;
+    }
+    #[cfg(not(target_arch = "aarch64"))]
+    fmas! {
+        f64x2 (2): f64x2_mul_add, f64x2_mul_sub, f64x2_neg_mul_add;
+    }
+
+    // the 256-bit vectors work on each half, so they use the native
+    // instructions where the 128-bit ones do.
+    macro_rules! halves {
+        ($($ty: ident ($shuffle: ident $idx: tt): $($func: ident ($half: ident)),*;)*) => {
+            $($(
+                #[inline]
+                pub fn $func(x: $ty, y: $ty, z: $ty) -> $ty {
+                    let lo = $half(x.low(), y.low(), z.low());
+                    let hi = $half(x.high(), y.high(), z.high());
+                    unsafe {$shuffle(lo, hi, $idx)}
+                }
+                )*)*
+        }
+    }
+
+    halves! {
+        f32x8 (simd_shuffle8 [0, 1, 2, 3, 4, 5, 6, 7]):
+            f32x8_mul_add (f32x4_mul_add), f32x8_mul_sub (f32x4_mul_sub),
+            f32x8_neg_mul_add (f32x4_neg_mul_add);
+        f64x4 (simd_shuffle4 [0, 1, 2, 3]):
+            f64x4_mul_add (f64x2_mul_add), f64x4_mul_sub (f64x2_mul_sub),
+            f64x4_neg_mul_add (f64x2_neg_mul_add);
+    }
+}
 
 macro_rules! basic_impls {
     ($(
@@ -92,6 +194,39 @@ macro_rules! basic_impls {
                 unsafe {simd_insert(self, idx, elem)}
             }
 
+            /// Add `other` to the lanes of `self` where `mask` is
+            /// true, leaving the other lanes unchanged.
+            ///
+            /// This is equivalent to `mask.select(self + other, self)`.
+            #[inline]
+            pub fn add_masked(self, mask: $bool, other: Self) -> Self {
+                mask.select(self + other, self)
+            }
+            /// Replace the lanes of `self` where `mask` is true with
+            /// the corresponding lanes of `other`.
+            ///
+            /// This is equivalent to `*self = mask.select(other, *self)`.
+            #[inline]
+            pub fn assign_where(&mut self, mask: $bool, other: Self) {
+                *self = mask.select(other, *self);
+            }
+
+            /// Take the lanes of `other` where bit `i` of `mask` is
+            /// set, and the lanes of `self` elsewhere.
+            ///
+            /// This is `Self::Bool::from_bitmask(mask).select(other,
+            /// self)`. `mask` is an ordinary argument, not an
+            /// immediate: the compiler can't express a constant
+            /// parameter, so a mask that isn't known at compile time
+            /// gives a variable blend (or the bitwise sequence). When
+            /// it is a constant, optimized builds fold it to a single
+            /// immediate blend (e.g. `blendps` or `pblendw`) where the
+            /// platform has one; `tests/blend_asm.rs` checks this.
+            #[inline]
+            pub fn blend_bitmask(self, other: Self, mask: u64) -> Self {
+                $bool::from_bitmask(mask).select(other, self)
+            }
+
             /// Load a new value from the `idx`th position of `array`.
             ///
             /// This is equivalent to the following, but is possibly
@@ -137,6 +272,128 @@ macro_rules! basic_impls {
                     *(place.as_mut_ptr() as *mut Unalign<Self>) = Unalign(self)
                 }
             }
+        }
+
+        // These add (or multiply) in iteration order, so the results
+        // don't depend on the platform.
+        impl ::std::iter::Sum for $name {
+            #[inline]
+            fn sum<I: Iterator<Item = $name>>(iter: I) -> $name {
+                iter.fold($name::splat(0 as $elem), |a, b| a + b)
+            }
+        }
+        impl<'a> ::std::iter::Sum<&'a $name> for $name {
+            #[inline]
+            fn sum<I: Iterator<Item = &'a $name>>(iter: I) -> $name {
+                iter.fold($name::splat(0 as $elem), |a, b| a + *b)
+            }
+        }
+        impl ::std::iter::Product for $name {
+            #[inline]
+            fn product<I: Iterator<Item = $name>>(iter: I) -> $name {
+                iter.fold($name::splat(1 as $elem), |a, b| a * b)
+            }
+        }
+        impl<'a> ::std::iter::Product<&'a $name> for $name {
+            #[inline]
+            fn product<I: Iterator<Item = &'a $name>>(iter: I) -> $name {
+                iter.fold($name::splat(1 as $elem), |a, b| a * *b)
+            }
+        }
+
+        /// Collect exactly as many elements as there are lanes.
+        ///
+        /// # Panics
+        ///
+        /// `collect` will panic if the iterator yields fewer or
+        /// more elements than that.
+        impl ::std::iter::FromIterator<$elem> for $name {
+            fn from_iter<I: IntoIterator<Item = $elem>>(iter: I) -> $name {
+                let mut iter = iter.into_iter();
+                let mut ret = $name::splat(0 as $elem);
+                for i in 0..$length {
+                    match iter.next() {
+                        Some(x) => ret = ret.replace(i, x),
+                        None => panic!("expected {} elements, found {}", $length, i),
+                    }
+                }
+                assert!(iter.next().is_none(),
+                        "expected {} elements, found more", $length);
+                ret
+            }
+        }
+
+        /// Two vectors are equal if every pair of lanes is equal.
+        impl PartialEq for $name {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                $name::eq(*self, *other).all()
+            }
+        }
+        impl Default for $name {
+            #[inline]
+            fn default() -> Self {
+                $name::splat(0 as $elem)
+            }
+        }
+
+        /// Formats as `[a, b, c, d]`, with any flags applied to each
+        /// lane.
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                let mut lanes = [0 as $elem; $length];
+                self.store(&mut lanes, 0);
+                fmt_lanes(&lanes, f, <$elem as ::std::fmt::Display>::fmt)
+            }
+        }
+        /// Parses the form written by `Display`, `[a, b, c, d]`.
+        impl ::std::str::FromStr for $name {
+            type Err = ParseVectorError;
+
+            fn from_str(s: &str) -> Result<Self, ParseVectorError> {
+                let mut lanes = [0 as $elem; $length];
+                parse_lanes(s, &mut lanes)?;
+                Ok($name::load(&lanes, 0))
+            }
+        })*
+    }
+}
+
+// The traits that only make sense for integer lanes.
+macro_rules! int_trait_impls {
+    ($($name: ident: $elem: ident, $length: expr;)*) => {
+        $(impl Eq for $name {}
+        impl ::std::hash::Hash for $name {
+            fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+                let mut lanes = [0 as $elem; $length];
+                self.store(&mut lanes, 0);
+                <$elem as ::std::hash::Hash>::hash_slice(&lanes, state)
+            }
+        }
+
+        /// Formats as `[a, b, c, d]`, with each lane in hexadecimal.
+        impl ::std::fmt::LowerHex for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                let mut lanes = [0 as $elem; $length];
+                self.store(&mut lanes, 0);
+                fmt_lanes(&lanes, f, <$elem as ::std::fmt::LowerHex>::fmt)
+            }
+        }
+        /// Formats as `[a, b, c, d]`, with each lane in hexadecimal.
+        impl ::std::fmt::UpperHex for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                let mut lanes = [0 as $elem; $length];
+                self.store(&mut lanes, 0);
+                fmt_lanes(&lanes, f, <$elem as ::std::fmt::UpperHex>::fmt)
+            }
+        }
+        /// Formats as `[a, b, c, d]`, with each lane in binary.
+        impl ::std::fmt::Binary for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                let mut lanes = [0 as $elem; $length];
+                self.store(&mut lanes, 0);
+                fmt_lanes(&lanes, f, <$elem as ::std::fmt::Binary>::fmt)
+            }
         })*
     }
 }
@@ -144,7 +401,7 @@ macro_rules! basic_impls {
 basic_impls! {
     u32x4: u32, bool32ix4, simd_shuffle4, 4, x0, x1 | x2, x3;
     i32x4: i32, bool32ix4, simd_shuffle4, 4, x0, x1 | x2, x3;
-    f32x4: f32, bool32fx4, simd_shuffle4, 4, x0, x1 | x2, x3;
+    f32x4: f32, bool32ix4, simd_shuffle4, 4, x0, x1 | x2, x3;
 
     u16x8: u16, bool16ix8, simd_shuffle8, 8, x0, x1, x2, x3 | x4, x5, x6, x7;
     i16x8: i16, bool16ix8, simd_shuffle8, 8, x0, x1, x2, x3 | x4, x5, x6, x7;
@@ -152,13 +409,598 @@ basic_impls! {
     u8x16: u8, bool8ix16, simd_shuffle16, 16, x0, x1, x2, x3, x4, x5, x6, x7 | x8, x9, x10, x11, x12, x13, x14, x15;
     i8x16: i8, bool8ix16, simd_shuffle16, 16, x0, x1, x2, x3, x4, x5, x6, x7 | x8, x9, x10, x11, x12, x13, x14, x15;
 }
+int_trait_impls! {
+    u32x4: u32, 4;
+    i32x4: i32, 4;
+    u16x8: u16, 8;
+    i16x8: i16, 8;
+    u8x16: u8, 16;
+    i8x16: i8, 16;
+}
+
+macro_rules! byte_order_impls {
+    (@swap $x: expr, u8x16) => ($x);
+    (@swap $x: expr, u8x32) => ($x);
+    (@swap $x: expr, $uint: ident) => ({
+        let x: $uint = bitcast($x);
+        bitcast(x.swap_bytes())
+    });
+    ($($name: ident: $uint: ident, $size: expr;)*) => {
+        $(impl $name {
+            /// Create a vector from its representation as bytes in
+            /// native byte order.
+            #[inline]
+            pub fn from_ne_bytes(bytes: &[u8; $size]) -> Self {
+                unsafe {mem::transmute(*bytes)}
+            }
+            /// Create a vector from its representation as bytes, with
+            /// each lane in little endian byte order.
+            #[inline]
+            pub fn from_le_bytes(bytes: &[u8; $size]) -> Self {
+                let x = $name::from_ne_bytes(bytes);
+                if cfg!(target_endian = "little") { x } else { x.swap_lane_bytes() }
+            }
+            /// Create a vector from its representation as bytes, with
+            /// each lane in big endian byte order.
+            #[inline]
+            pub fn from_be_bytes(bytes: &[u8; $size]) -> Self {
+                let x = $name::from_ne_bytes(bytes);
+                if cfg!(target_endian = "big") { x } else { x.swap_lane_bytes() }
+            }
+
+            /// Return the representation of `self` as bytes in native
+            /// byte order.
+            #[inline]
+            pub fn to_ne_bytes(self) -> [u8; $size] {
+                unsafe {mem::transmute(self)}
+            }
+            /// Return the representation of `self` as bytes, with each
+            /// lane in little endian byte order.
+            #[inline]
+            pub fn to_le_bytes(self) -> [u8; $size] {
+                let x = if cfg!(target_endian = "little") { self } else { self.swap_lane_bytes() };
+                x.to_ne_bytes()
+            }
+            /// Return the representation of `self` as bytes, with each
+            /// lane in big endian byte order.
+            #[inline]
+            pub fn to_be_bytes(self) -> [u8; $size] {
+                let x = if cfg!(target_endian = "big") { self } else { self.swap_lane_bytes() };
+                x.to_ne_bytes()
+            }
+
+            /// Load a vector from `bytes`, starting at byte `offset`,
+            /// with each lane in little endian byte order.
+            ///
+            /// # Panics
+            ///
+            /// `load_le` will panic if `offset + size_of::<Self>()` is
+            /// larger than `bytes.len()`.
+            #[inline]
+            pub fn load_le(bytes: &[u8], offset: usize) -> Self {
+                let data = &bytes[offset..offset + $size];
+                $name::from_le_bytes(unsafe {&*(data.as_ptr() as *const [u8; $size])})
+            }
+            /// Load a vector from `bytes`, starting at byte `offset`,
+            /// with each lane in big endian byte order.
+            ///
+            /// # Panics
+            ///
+            /// `load_be` will panic if `offset + size_of::<Self>()` is
+            /// larger than `bytes.len()`.
+            #[inline]
+            pub fn load_be(bytes: &[u8], offset: usize) -> Self {
+                let data = &bytes[offset..offset + $size];
+                $name::from_be_bytes(unsafe {&*(data.as_ptr() as *const [u8; $size])})
+            }
+            /// Store `self` to `bytes`, starting at byte `offset`, with
+            /// each lane in little endian byte order.
+            ///
+            /// # Panics
+            ///
+            /// `store_le` will panic if `offset + size_of::<Self>()` is
+            /// larger than `bytes.len()`.
+            #[inline]
+            pub fn store_le(self, bytes: &mut [u8], offset: usize) {
+                bytes[offset..offset + $size].copy_from_slice(&self.to_le_bytes())
+            }
+            /// Store `self` to `bytes`, starting at byte `offset`, with
+            /// each lane in big endian byte order.
+            ///
+            /// # Panics
+            ///
+            /// `store_be` will panic if `offset + size_of::<Self>()` is
+            /// larger than `bytes.len()`.
+            #[inline]
+            pub fn store_be(self, bytes: &mut [u8], offset: usize) {
+                bytes[offset..offset + $size].copy_from_slice(&self.to_be_bytes())
+            }
+
+            // `swap_bytes` on the unsigned lanes, which is a single
+            // byte shuffle with SSSE3, AVX2 and NEON.
+            #[inline]
+            fn swap_lane_bytes(self) -> Self {
+                byte_order_impls!(@swap self, $uint)
+            }
+        })*
+    }
+}
+
+macro_rules! bit_reversal_impls {
+    ($($name: ident: $bytes: ident, $swap: ident;)*) => {
+        $(impl $name {
+            /// Reverse the order of the bytes in each lane.
+            #[inline]
+            pub fn swap_bytes(self) -> Self {
+                bitcast(common::$swap(bitcast(self)))
+            }
+            /// Reverse the order of the bits in each lane.
+            #[inline]
+            pub fn reverse_bits(self) -> Self {
+                let bytes: $bytes = bitcast(self.swap_bytes());
+                bitcast(bytes.reverse_bits())
+            }
+        })*
+    }
+}
+
+// `swap_bytes` and `reverse_bits` for backends without a byte
+// shuffle: each step swaps the adjacent groups of `$shift` bits
+// selected by `$mask`.
+#[allow(unused_macros)]
+macro_rules! bit_group_swaps {
+    ($($func: ident ($ty: ident) [$($shift: expr => $mask: expr),*];)*) => {
+        $(
+            #[inline]
+            pub fn $func(x: $ty) -> $ty {
+                let mut x = x;
+                $(
+                    let mask = $ty::splat($mask);
+                    x = ((x >> ($shift as u32)) & mask) | ((x & mask) << ($shift as u32));
+                    )*
+                x
+            }
+            )*
+    }
+}
+
+// `swap_bytes` as a shuffle of the bytes of a 128-bit vector, which
+// LLVM turns into `vrev16`/`vrev32`/`vrev64` on NEON.
+#[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+macro_rules! byte_shuffle_swaps {
+    ($($func: ident ($ty: ident) [$($idx: expr),*];)*) => {
+        $(
+            #[inline]
+            pub fn $func(x: $ty) -> $ty {
+                let bytes: u8x16 = bitcast(x);
+                let swapped: u8x16 = unsafe {simd_shuffle16(bytes, bytes, [$($idx),*])};
+                bitcast(swapped)
+            }
+            )*
+    }
+}
+
+// `swap_bytes` and `reverse_bits` for 256-bit vectors via the 128-bit
+// halves.
+#[allow(unused_macros)]
+macro_rules! half_bit_swaps {
+    ($($func: ident ($ty: ident): $method: ident, $shuffle: ident [$($idx: expr),*];)*) => {
+        $(
+            #[inline]
+            pub fn $func(x: $ty) -> $ty {
+                let lo = x.low().$method();
+                let hi = x.high().$method();
+                unsafe {$shuffle(lo, hi, [$($idx),*])}
+            }
+            )*
+    }
+}
+
+byte_order_impls! {
+    u8x16: u8x16, 16;
+    i8x16: u8x16, 16;
+    u16x8: u16x8, 16;
+    i16x8: u16x8, 16;
+    u32x4: u32x4, 16;
+    i32x4: u32x4, 16;
+    f32x4: u32x4, 16;
+}
+
+// Check the byte order conversions against lanes assembled by hand from
+// `$lane`-byte groups, and the slice loads and stores against the array
+// forms. `$uint` is the unsigned vector with the same lanes as `$ty`.
+#[cfg(test)]
+macro_rules! check_byte_order {
+    ($ty: ident, $uint: ident, $size: expr, $lane: expr) => {{
+        let mut bytes = [0u8; $size];
+        for (i, b) in bytes.iter_mut().enumerate() {
+            *b = (i as u8).wrapping_mul(37).wrapping_add(1);
+        }
+        let le = $ty::from_le_bytes(&bytes);
+        let be = $ty::from_be_bytes(&bytes);
+        let ne = $ty::from_ne_bytes(&bytes);
+        let le_bits: $uint = ::bitcast(le);
+        let be_bits: $uint = ::bitcast(be);
+        let ne_bits: $uint = ::bitcast(ne);
+        for i in 0..$size / $lane {
+            let (mut want_le, mut want_be) = (0u64, 0u64);
+            for j in 0..$lane {
+                want_le |= (bytes[i * $lane + j] as u64) << (8 * j);
+                want_be = want_be << 8 | bytes[i * $lane + j] as u64;
+            }
+            assert_eq!(le_bits.extract(i as u32) as u64, want_le, "{}: le lane {}", stringify!($ty), i);
+            assert_eq!(be_bits.extract(i as u32) as u64, want_be, "{}: be lane {}", stringify!($ty), i);
+        }
+        if cfg!(target_endian = "little") {
+            assert_eq!(ne_bits, le_bits);
+        } else {
+            assert_eq!(ne_bits, be_bits);
+        }
+
+        assert_eq!(le.to_le_bytes(), bytes);
+        assert_eq!(be.to_be_bytes(), bytes);
+        assert_eq!(ne.to_ne_bytes(), bytes);
+
+        let mut buf = [0u8; $size + 3];
+        buf[3..].copy_from_slice(&bytes);
+        let loaded: $uint = ::bitcast($ty::load_le(&buf, 3));
+        assert_eq!(loaded, le_bits);
+        let loaded: $uint = ::bitcast($ty::load_be(&buf, 3));
+        assert_eq!(loaded, be_bits);
+
+        let mut out = [0u8; $size + 3];
+        le.store_le(&mut out, 3);
+        assert_eq!(&out[3..], &bytes[..]);
+        be.store_be(&mut out, 1);
+        assert_eq!(&out[1..$size + 1], &bytes[..]);
+    }}
+}
+
+#[cfg(test)]
+mod byte_order_tests {
+    use super::*;
+
+    #[test]
+    fn u8x16_byte_order() {
+        check_byte_order!(u8x16, u8x16, 16, 1);
+    }
+    #[test]
+    fn i8x16_byte_order() {
+        check_byte_order!(i8x16, u8x16, 16, 1);
+    }
+    #[test]
+    fn u16x8_byte_order() {
+        check_byte_order!(u16x8, u16x8, 16, 2);
+    }
+    #[test]
+    fn i16x8_byte_order() {
+        check_byte_order!(i16x8, u16x8, 16, 2);
+    }
+    #[test]
+    fn u32x4_byte_order() {
+        check_byte_order!(u32x4, u32x4, 16, 4);
+    }
+    #[test]
+    fn i32x4_byte_order() {
+        check_byte_order!(i32x4, u32x4, 16, 4);
+    }
+    #[test]
+    fn f32x4_byte_order() {
+        check_byte_order!(f32x4, u32x4, 16, 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn load_be_short_slice() {
+        u32x4::load_be(&[0u8; 15], 0);
+    }
+    #[test]
+    #[should_panic]
+    fn load_le_past_end() {
+        u32x4::load_le(&[0u8; 20], 5);
+    }
+    #[test]
+    #[should_panic]
+    fn store_be_short_slice() {
+        u32x4::splat(1).store_be(&mut [0u8; 15], 0);
+    }
+}
+
+bit_reversal_impls! {
+    u16x8: u8x16, u16x8_swap_bytes;
+    i16x8: u8x16, u16x8_swap_bytes;
+    u32x4: u8x16, u32x4_swap_bytes;
+    i32x4: u8x16, u32x4_swap_bytes;
+}
+impl u8x16 {
+    /// Reverse the order of the bits in each lane.
+    #[inline]
+    pub fn reverse_bits(self) -> Self {
+        common::u8x16_reverse_bits(self)
+    }
+}
+
+// Check `swap_bytes` and `reverse_bits` on `$ty` lane by lane against
+// the scalar versions, over lanes whose low byte takes every value, so
+// that each entry of the nibble tables used by the SSSE3 and AVX2
+// versions is looked up. `$uint` is the unsigned vector with the same
+// lanes as `$ty`, with elements `$uelem`.
+#[cfg(test)]
+macro_rules! check_bit_reversal {
+    (@lanes $uint: ident, $uelem: ty, $length: expr, |$x: ident, $i: ident, $want: ident| $check: expr) => {{
+        for round in 0..256 / $length {
+            let mut $x = $uint::splat(0);
+            for j in 0..$length {
+                let k = (round * $length + j) as u64;
+                let high = k.wrapping_mul(0x9e3779b97f4a7c15) as $uelem & !(0xff as $uelem);
+                $x = $x.replace(j as u32, high | k as $uelem);
+            }
+            for $i in 0..$length as u32 {
+                let $want = $x.extract($i);
+                $check;
+            }
+        }
+    }};
+    (reverse_bits: $ty: ident, $uint: ident, $uelem: ty, $length: expr) => {
+        check_bit_reversal!(@lanes $uint, $uelem, $length, |x, i, lane| {
+            let bits = ::std::mem::size_of::<$uelem>() * 8;
+            let mut want: $uelem = 0;
+            for b in 0..bits {
+                if (lane >> b) & 1 == 1 {
+                    want |= (1 as $uelem) << (bits - 1 - b);
+                }
+            }
+            let y: $ty = ::bitcast(x);
+            let got: $uint = ::bitcast(y.reverse_bits());
+            assert_eq!(got.extract(i), want, "{}::reverse_bits lane {} of {:?}", stringify!($ty), i, x);
+        })
+    };
+    (swap_bytes: $ty: ident, $uint: ident, $uelem: ty, $length: expr) => {
+        check_bit_reversal!(@lanes $uint, $uelem, $length, |x, i, lane| {
+            let y: $ty = ::bitcast(x);
+            let got: $uint = ::bitcast(y.swap_bytes());
+            assert_eq!(got.extract(i), lane.swap_bytes(), "{}::swap_bytes lane {} of {:?}", stringify!($ty), i, x);
+        })
+    };
+}
+
+#[cfg(test)]
+mod bit_reversal_tests {
+    use super::*;
+
+    #[test]
+    fn u8x16_reverse_bits() {
+        check_bit_reversal!(reverse_bits: u8x16, u8x16, u8, 16);
+    }
+    #[test]
+    fn u16x8_bit_reversal() {
+        check_bit_reversal!(swap_bytes: u16x8, u16x8, u16, 8);
+        check_bit_reversal!(reverse_bits: u16x8, u16x8, u16, 8);
+    }
+    #[test]
+    fn i16x8_bit_reversal() {
+        check_bit_reversal!(swap_bytes: i16x8, u16x8, u16, 8);
+        check_bit_reversal!(reverse_bits: i16x8, u16x8, u16, 8);
+    }
+    #[test]
+    fn u32x4_bit_reversal() {
+        check_bit_reversal!(swap_bytes: u32x4, u32x4, u32, 4);
+        check_bit_reversal!(reverse_bits: u32x4, u32x4, u32, 4);
+    }
+    #[test]
+    fn i32x4_bit_reversal() {
+        check_bit_reversal!(swap_bytes: i32x4, u32x4, u32, 4);
+        check_bit_reversal!(reverse_bits: i32x4, u32x4, u32, 4);
+    }
+}
+
+// Check that `Display` prints each window of `$xs` lane by lane, and
+// that `FromStr` parses it back. Comparing the strings rather than the
+// vectors also checks the sign of zero and NaN lanes.
+#[cfg(test)]
+macro_rules! check_display {
+    ($ty: ident, $elem: ty, $length: expr, [$($x: expr),*]) => {{
+        let xs: Vec<$elem> = vec![$($x),*];
+        for i in 0..xs.len() - $length + 1 {
+            let x = $ty::load(&xs, i);
+            let s = x.to_string();
+            let lanes: Vec<String> = xs[i..i + $length].iter().map(|x| x.to_string()).collect();
+            assert_eq!(s, format!("[{}]", lanes.join(", ")));
+            let y: $ty = s.parse().unwrap();
+            assert_eq!(y.to_string(), s);
+        }
+    }}
+}
+
+#[cfg(test)]
+mod format_tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn i8x16_display() {
+        check_display!(i8x16, i8, 16, [0, 1, -1, 127, -128, 42, -42, 7, 8, 9, 10, 11, 12, 13, 14,
+                                       15, 16]);
+    }
+    #[test]
+    fn u32x4_display() {
+        check_display!(u32x4, u32, 4, [0, 1, 4294967295, 2147483648, 12345]);
+    }
+    #[test]
+    fn f32x4_display() {
+        check_display!(f32x4, f32, 4, [0.0, -0.0, 0.1, -2.25, 1e-45, 3.4028235e38, 1.0 / 3.0,
+                                       ::std::f32::INFINITY, ::std::f32::NEG_INFINITY,
+                                       ::std::f32::NAN]);
+    }
+
+    #[test]
+    fn round_trip() {
+        let x = i32x4::new(-1, 0, 2147483647, -2147483648);
+        assert_eq!(x.to_string().parse::<i32x4>().unwrap(), x);
+        assert_eq!(" [ -1,0 ,  2147483647,-2147483648 ] ".parse::<i32x4>().unwrap(), x);
+        let m = bool32ix4::new(true, false, false, true);
+        assert_eq!(m.to_string(), "[true, false, false, true]");
+        assert_eq!(m.to_string().parse::<bool32ix4>().unwrap(), m);
+    }
+
+    #[test]
+    fn lane_flags() {
+        let x = u32x4::new(255, 0, 16, 1);
+        assert_eq!(format!("{:x}", x), "[ff, 0, 10, 1]");
+        assert_eq!(format!("{:#X}", x), "[0xFF, 0x0, 0x10, 0x1]");
+        assert_eq!(format!("{:04b}", u8x16::splat(5)).matches("0101").count(), 16);
+        assert_eq!(format!("{:.1}", f32x4::new(1.26, -0.5, 2.0, 3.0)), "[1.3, -0.5, 2.0, 3.0]");
+    }
+
+    #[test]
+    fn parse_errors() {
+        use {ParseVectorError, ParseVectorErrorKind};
+        let err = |kind| Err(ParseVectorError { kind: kind });
+        assert_eq!("1, 2, 3, 4".parse::<u32x4>(), err(ParseVectorErrorKind::Brackets));
+        assert_eq!("[1, 2, 3, 4".parse::<u32x4>(), err(ParseVectorErrorKind::Brackets));
+        assert_eq!("[1, 2, 3]".parse::<u32x4>(), err(ParseVectorErrorKind::LaneCount));
+        assert_eq!("[1, 2, 3, 4, 5]".parse::<u32x4>(), err(ParseVectorErrorKind::LaneCount));
+        assert_eq!("[1, x, 3, 4]".parse::<u32x4>(), err(ParseVectorErrorKind::Lane));
+        assert_eq!("[1, -2, 3, 4]".parse::<u32x4>(), err(ParseVectorErrorKind::Lane));
+        assert_eq!("[1, 2, 3, 4]".parse::<u32x4>(), Ok(u32x4::new(1, 2, 3, 4)));
+    }
+
+    #[test]
+    fn eq_default_hash() {
+        assert_eq!(f32x4::default(), f32x4::splat(0.0));
+        assert_eq!(f32x4::splat(0.0), f32x4::splat(-0.0));
+        assert!(f32x4::splat(::std::f32::NAN) != f32x4::splat(::std::f32::NAN));
+        assert!(i16x8::splat(1) != i16x8::splat(1).replace(7, 2));
+        assert_eq!(bool8ix16::default(), bool8ix16::splat(false));
+
+        let set: HashSet<u16x8> = (0..10).map(|i| u16x8::splat(i % 4)).collect();
+        assert_eq!(set.len(), 4);
+        let set: HashSet<bool32ix4> = (0..16).map(|i| bool32ix4::from_bitmask(i % 5)).collect();
+        assert_eq!(set.len(), 5);
+    }
+}
+
+// Check `add_masked`, `assign_where`, `select_or_zero` and
+// `blend_bitmask` lane by lane against the mask bits.
+#[cfg(test)]
+macro_rules! check_masked_ops {
+    ($ty: ident, $elem: ident, $bool: ident, $length: expr) => {{
+        let xs: Vec<$elem> = (0..$length).map(|i| (i + 1) as $elem).collect();
+        let ys: Vec<$elem> = (0..$length).map(|i| (3 * i + 2) as $elem).collect();
+        let (x, y) = ($ty::load(&xs, 0), $ty::load(&ys, 0));
+        for &bits in &[0, !0, 0x5555_5555_5555_5555, 0xdead_beef_0123_4567] {
+            let mask = $bool::from_bitmask(bits);
+            let added = x.add_masked(mask, y);
+            let mut assigned = x;
+            assigned.assign_where(mask, y);
+            let zeroed = mask.select_or_zero(y);
+            let blended = x.blend_bitmask(y, bits);
+            for i in 0..$length {
+                let on = bits >> i & 1 != 0;
+                let (x, y) = (x.extract(i), y.extract(i));
+                assert_eq!(added.extract(i), if on { x + y } else { x });
+                assert_eq!(assigned.extract(i), if on { y } else { x });
+                assert_eq!(zeroed.extract(i), if on { y } else { 0 as $elem });
+                assert_eq!(blended.extract(i), if on { y } else { x });
+            }
+        }
+    }}
+}
+
+#[cfg(test)]
+mod masked_op_tests {
+    use super::*;
+
+    #[test]
+    fn f32x4_masked_ops() {
+        check_masked_ops!(f32x4, f32, bool32ix4, 4);
+    }
+    #[test]
+    fn u32x4_masked_ops() {
+        check_masked_ops!(u32x4, u32, bool32ix4, 4);
+    }
+    #[test]
+    fn i16x8_masked_ops() {
+        check_masked_ops!(i16x8, i16, bool16ix8, 8);
+    }
+    #[test]
+    fn u8x16_masked_ops() {
+        check_masked_ops!(u8x16, u8, bool8ix16, 16);
+    }
+}
+
+// Check `Sum`, `Product` and `FromIterator`, by value and by
+// reference, and on empty iterators.
+#[cfg(test)]
+macro_rules! check_iter_traits {
+    ($ty: ident, $elem: ident, $length: expr) => {{
+        let vs: Vec<$ty> = (0..5).map(|i| $ty::splat((i + 1) as $elem)).collect();
+        let sums = [vs.iter().cloned().sum::<$ty>(), vs.iter().sum::<$ty>()];
+        let products = [vs.iter().cloned().product::<$ty>(), vs.iter().product::<$ty>()];
+        let empty: [$ty; 0] = [];
+        let (zero, one) = (empty.iter().sum::<$ty>(), empty.iter().product::<$ty>());
+        for i in 0..$length {
+            for (s, p) in sums.iter().zip(&products) {
+                assert_eq!(s.extract(i), 15 as $elem);
+                assert_eq!(p.extract(i), 120 as $elem);
+            }
+            assert_eq!(zero.extract(i), 0 as $elem);
+            assert_eq!(one.extract(i), 1 as $elem);
+        }
+
+        let x: $ty = (0..$length).map(|i| (i + 1) as $elem).collect();
+        for i in 0..$length {
+            assert_eq!(x.extract(i), (i + 1) as $elem);
+        }
+    }}
+}
+
+#[cfg(test)]
+mod iter_trait_tests {
+    use super::*;
+
+    #[test]
+    fn f32x4_iter_traits() {
+        check_iter_traits!(f32x4, f32, 4);
+    }
+    #[test]
+    fn u32x4_iter_traits() {
+        check_iter_traits!(u32x4, u32, 4);
+    }
+    #[test]
+    fn i8x16_iter_traits() {
+        check_iter_traits!(i8x16, i8, 16);
+    }
+    #[test]
+    #[should_panic(expected = "expected 4 elements, found 3")]
+    fn from_iter_too_few() {
+        let _: f32x4 = vec![1.0, 2.0, 3.0].into_iter().collect();
+    }
+    #[test]
+    #[should_panic(expected = "expected 4 elements, found more")]
+    fn from_iter_too_many() {
+        let _: i32x4 = (0..5).collect();
+    }
+}
+
+// `select` for backends without a variable blend instruction.
+macro_rules! bitwise_selects {
+    ($($name: ident: $select: ident ($repr: ty);)*) => {
+        $(
+            #[inline]
+            pub fn $select(mask: $name, then: $repr, else_: $repr) -> $repr {
+                (then & mask.to_repr()) | (else_ & (!mask).to_repr())
+            }
+            )*
+    }
+}
 
 macro_rules! bool_impls {
     ($(
         $name: ident:
-        $elem: ident, $repr: ident, $repr_elem: ident, $length: expr, $all: ident, $any: ident,
+        $elem: ident, $repr: ident, $repr_elem: ident, $length: expr, $all: ident, $any: ident, $bitmask: ident, $select: ident,
         $($first: ident),* | $($last: ident),*
-        [$(#[$cvt_meta: meta] $cvt: ident -> $cvt_to: ident),*];
+        [$($(#[$cvt_meta: meta])* $cvt: ident -> $cvt_to: ident),*];
         )*) => {
         $(impl $name {
             /// Convert to integer representation.
@@ -228,11 +1070,20 @@ macro_rules! bool_impls {
             /// ```
             #[inline]
             pub fn select<T: Simd<Bool = $name>>(self, then: T, else_: T) -> T {
-                let then: $repr = bitcast(then);
-                let else_: $repr = bitcast(else_);
-                bitcast((then & self.to_repr()) | (else_ & (!self).to_repr()))
+                bitcast(common::$select(self, bitcast(then), bitcast(else_)))
             }
-
+            /// Select the elements of `x` where `self` is true, and
+            /// zero elsewhere.
+            ///
+            /// This is equivalent to `self.select(x, zero)`, where
+            /// every lane of `zero` is zero, but doesn't need the zero
+            /// vector.
+            #[inline]
+            pub fn select_or_zero<T: Simd<Bool = $name>>(self, x: T) -> T {
+                let x: $repr = bitcast(x);
+                bitcast(x & self.to_repr())
+            }
+
             /// Check if every element of `self` is true.
             ///
             /// This is equivalent to the following, but is possibly
@@ -258,8 +1109,75 @@ macro_rules! bool_impls {
                 common::$any(self)
             }
 
+            /// Check if every element of `self` is false.
+            ///
+            /// This is equivalent to `!self.any()`.
+            #[inline]
+            pub fn none(self) -> bool {
+                !self.any()
+            }
+            /// Count the elements of `self` that are true.
+            #[inline]
+            pub fn count_true(self) -> usize {
+                self.to_bitmask().count_ones() as usize
+            }
+            /// Find the index of the first element of `self` that is
+            /// true, if any.
+            #[inline]
+            pub fn first_true(self) -> Option<usize> {
+                let bits = self.to_bitmask();
+                if bits == 0 {
+                    None
+                } else {
+                    Some(bits.trailing_zeros() as usize)
+                }
+            }
+            /// Find the index of the last element of `self` that is
+            /// true, if any.
+            #[inline]
+            pub fn last_true(self) -> Option<usize> {
+                let bits = self.to_bitmask();
+                if bits == 0 {
+                    None
+                } else {
+                    Some(63 - bits.leading_zeros() as usize)
+                }
+            }
+            /// Iterate over the indices of the elements of `self` that
+            /// are true, in increasing order.
+            #[inline]
+            pub fn true_lanes(self) -> TrueLanes {
+                TrueLanes { bits: self.to_bitmask() }
+            }
+
+            /// Pack `self` into the low bits of an integer, with lane
+            /// `i` in bit `i`.
+            ///
+            /// This is equivalent to the following, but is possibly
+            /// more efficient:
+            ///
+            /// ```rust,ignore
+            /// (self.extract(0) as u64) | (self.extract(1) as u64) << 1 | ...
+            /// ```
+            #[inline]
+            pub fn to_bitmask(self) -> u64 {
+                common::$bitmask(self)
+            }
+            /// Create a new instance from the low bits of `bits`, with
+            /// lane `i` taken from bit `i`.
+            ///
+            /// Bits above the number of lanes are ignored.
+            #[allow(unused_assignments)]
+            #[inline]
+            pub fn from_bitmask(bits: u64) -> Self {
+                let mut i = 0;
+                $(let $first = bits >> i & 1 != 0; i += 1;)*
+                $(let $last = bits >> i & 1 != 0; i += 1;)*
+                $name::new($($first),*, $($last),*)
+            }
+
             $(
-                #[$cvt_meta]
+                $(#[$cvt_meta])*
                 #[inline]
                 pub fn $cvt(self) -> $cvt_to {
                     bitcast(self)
@@ -274,21 +1192,175 @@ macro_rules! bool_impls {
                   Self::from_repr($repr::splat(!(0 as $repr_elem)) ^ self.to_repr())
               }
           }
+
+          impl PartialEq for $name {
+              #[inline]
+              fn eq(&self, other: &Self) -> bool {
+                  self.to_repr() == other.to_repr()
+              }
+          }
+          impl Eq for $name {}
+          impl ::std::hash::Hash for $name {
+              fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+                  ::std::hash::Hash::hash(&self.to_bitmask(), state)
+              }
+          }
+          /// All lanes are false.
+          impl Default for $name {
+              #[inline]
+              fn default() -> Self {
+                  $name::splat(false)
+              }
+          }
+
+          /// Formats as `[true, false, ...]`.
+          impl ::std::fmt::Display for $name {
+              fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                  let mut lanes = [false; $length];
+                  for (i, lane) in lanes.iter_mut().enumerate() {
+                      *lane = self.extract(i as u32);
+                  }
+                  fmt_lanes(&lanes, f, <bool as ::std::fmt::Display>::fmt)
+              }
+          }
+          /// Parses the form written by `Display`, `[true, false, ...]`.
+          impl ::std::str::FromStr for $name {
+              type Err = ParseVectorError;
+
+              fn from_str(s: &str) -> Result<Self, ParseVectorError> {
+                  let mut lanes = [false; $length];
+                  parse_lanes(s, &mut lanes)?;
+                  let mut ret = $name::splat(false);
+                  for (i, &lane) in lanes.iter().enumerate() {
+                      ret = ret.replace(i as u32, lane);
+                  }
+                  Ok(ret)
+              }
+          }
           )*
     }
 }
 
 bool_impls! {
-    bool32ix4: bool32i, i32x4, i32, 4, bool32ix4_all, bool32ix4_any, x0, x1 | x2, x3
+    bool32ix4: bool32i, i32x4, i32, 4, bool32ix4_all, bool32ix4_any, bool32ix4_bitmask, bool32ix4_select, x0, x1 | x2, x3
         [/// Convert `self` to a boolean vector for interacting with floating point vectors.
-         to_f -> bool32fx4];
-    bool32fx4: bool32f, i32x4, i32, 4, bool32fx4_all, bool32fx4_any, x0, x1 | x2, x3
-        [/// Convert `self` to a boolean vector for interacting with integer vectors.
+         #[deprecated(note = "`bool32ix4` works with floating point vectors directly")]
+         to_f -> bool32ix4,
+         /// Convert `self` to a boolean vector for interacting with integer vectors.
+         #[deprecated(note = "`bool32ix4` works with integer vectors directly")]
          to_i -> bool32ix4];
 
-    bool16ix8: bool16i, i16x8, i16, 8, bool16ix8_all, bool16ix8_any, x0, x1, x2, x3 | x4, x5, x6, x7 [];
+    bool16ix8: bool16i, i16x8, i16, 8, bool16ix8_all, bool16ix8_any, bool16ix8_bitmask, bool16ix8_select, x0, x1, x2, x3 | x4, x5, x6, x7 [];
+
+    bool8ix16: bool8i, i8x16, i8, 16, bool8ix16_all, bool8ix16_any, bool8ix16_bitmask, bool8ix16_select, x0, x1, x2, x3, x4, x5, x6, x7 | x8, x9, x10, x11, x12, x13, x14, x15 [];
+}
+
+// Check that `to_bitmask` and `from_bitmask` round-trip, and agree
+// with `extract`.
+#[cfg(test)]
+macro_rules! check_bitmask {
+    ($ty: ident, $length: expr) => {{
+        let lanes = !0u64 >> (64 - $length);
+        let mut masks = vec![0, !0, 0x5555_5555_5555_5555, 0xdead_beef_0123_4567];
+        masks.extend((0..64).map(|i| 1u64 << i));
+        for &bits in &masks {
+            let mask = $ty::from_bitmask(bits);
+            assert_eq!(mask.to_bitmask(), bits & lanes, "{:x}", bits);
+            for i in 0..$length {
+                assert_eq!(mask.extract(i), bits >> i & 1 != 0, "{:x}", bits);
+            }
+        }
+    }}
+}
+
+#[cfg(test)]
+mod bitmask_tests {
+    use super::*;
+
+    #[test]
+    fn bool32ix4_bitmask() {
+        check_bitmask!(bool32ix4, 4);
+    }
+    #[test]
+    fn bool16ix8_bitmask() {
+        check_bitmask!(bool16ix8, 8);
+    }
+    #[test]
+    fn bool8ix16_bitmask() {
+        check_bitmask!(bool8ix16, 16);
+    }
+}
+
+// Check `none`, `count_true`, `first_true`, `last_true` and
+// `true_lanes` against `extract`, with the iterator taken from both
+// ends.
+#[cfg(test)]
+macro_rules! check_lane_queries {
+    ($ty: ident, $length: expr) => {{
+        let empty = $ty::splat(false);
+        assert!(empty.none());
+        assert_eq!(empty.count_true(), 0);
+        assert_eq!(empty.first_true(), None);
+        assert_eq!(empty.last_true(), None);
+        let mut lanes = empty.true_lanes();
+        assert_eq!(lanes.len(), 0);
+        assert_eq!(lanes.next(), None);
+        assert_eq!(lanes.next_back(), None);
+
+        let full = $ty::splat(true);
+        assert!(!full.none());
+        assert_eq!(full.count_true(), $length);
+        assert_eq!(full.first_true(), Some(0));
+        assert_eq!(full.last_true(), Some($length - 1));
+        assert_eq!(full.true_lanes().len(), $length);
+        assert_eq!(full.true_lanes().collect::<Vec<_>>(), (0..$length).collect::<Vec<_>>());
+        assert_eq!(full.true_lanes().rev().collect::<Vec<_>>(),
+                   (0..$length).rev().collect::<Vec<_>>());
+
+        for &bits in &[0x5555_5555_5555_5555, 0xdead_beef_0123_4567, 1u64 << ($length - 1), 0x8001] {
+            let mask = $ty::from_bitmask(bits);
+            let expected: Vec<usize> = (0..$length).filter(|&i| mask.extract(i as u32)).collect();
+            assert_eq!(mask.none(), expected.is_empty());
+            assert_eq!(mask.count_true(), expected.len());
+            assert_eq!(mask.first_true(), expected.first().cloned());
+            assert_eq!(mask.last_true(), expected.last().cloned());
 
-    bool8ix16: bool8i, i8x16, i8, 16, bool8ix16_all, bool8ix16_any, x0, x1, x2, x3, x4, x5, x6, x7 | x8, x9, x10, x11, x12, x13, x14, x15 [];
+            let mut lanes = mask.true_lanes();
+            let (mut front, mut back) = (vec![], vec![]);
+            loop {
+                assert_eq!(lanes.len(), expected.len() - front.len() - back.len());
+                match lanes.next() {
+                    Some(i) => front.push(i),
+                    None => break,
+                }
+                match lanes.next_back() {
+                    Some(i) => back.push(i),
+                    None => break,
+                }
+            }
+            assert_eq!(lanes.next_back(), None);
+            front.extend(back.into_iter().rev());
+            assert_eq!(front, expected);
+        }
+    }}
+}
+
+#[cfg(test)]
+mod lane_query_tests {
+    use super::*;
+
+    #[test]
+    fn bool32ix4_lane_queries() {
+        check_lane_queries!(bool32ix4, 4);
+    }
+    #[test]
+    fn bool16ix8_lane_queries() {
+        check_lane_queries!(bool16ix8, 8);
+    }
+    #[test]
+    fn bool8ix16_lane_queries() {
+        check_lane_queries!(bool8ix16, 16);
+    }
 }
 
 impl u32x4 {
@@ -324,7 +1396,11 @@ impl f32x4 {
     /// Compute an approximation to the reciprocal of the square root
     /// of `self`, that is, `f32::splat(1.0) / self.sqrt()`.
     ///
-    /// The accuracy of this approximation is platform dependent.
+    /// The accuracy of this approximation is platform dependent:
+    /// at least 11 bits on x86 and 7 bits on ARM. `rsqrt_precise1`
+    /// and `rsqrt_precise2` refine it. With the `deterministic`
+    /// feature, this is computed with a division instead, and is the
+    /// same everywhere.
     #[inline]
     pub fn approx_rsqrt(self) -> Self {
         common::f32x4_approx_rsqrt(self)
@@ -332,48 +1408,40 @@ impl f32x4 {
     /// Compute an approximation to the reciprocal of `self`, that is,
     /// `f32::splat(1.0) / self`.
     ///
-    /// The accuracy of this approximation is platform dependent.
+    /// The accuracy of this approximation is platform dependent:
+    /// at least 11 bits on x86 and 7 bits on ARM. `recip_precise1`
+    /// and `recip_precise2` refine it. With the `deterministic`
+    /// feature, this is computed with a division instead, and is the
+    /// same everywhere.
     #[inline]
     pub fn approx_reciprocal(self) -> Self {
         common::f32x4_approx_reciprocal(self)
     }
-    /// Compute the lane-wise maximum of `self` and `other`.
-    ///
-    /// This is equivalent to the following, but is possibly more
-    /// efficient:
-    ///
-    /// ```rust,ignore
-    /// f32x4::new(self.extract(0).max(other.extract(0)),
-    ///            self.extract(1).max(other.extract(1)),
-    ///            ...)
-    /// ```
-    #[inline]
-    pub fn max(self, other: Self) -> Self {
-        common::f32x4_max(self, other)
-    }
-    /// Compute the lane-wise minimum of `self` and `other`.
-    ///
-    /// This is equivalent to the following, but is possibly more
-    /// efficient:
-    ///
-    /// ```rust,ignore
-    /// f32x4::new(self.extract(0).min(other.extract(0)),
-    ///            self.extract(1).min(other.extract(1)),
-    ///            ...)
-    /// ```
-    #[inline]
-    pub fn min(self, other: Self) -> Self {
-        common::f32x4_min(self, other)
-    }
     /// Convert each lane to a signed integer.
+    ///
+    /// NaN and out of range lanes give platform dependent results,
+    /// unless the `deterministic` feature is enabled, when this is
+    /// the same as `to_i32_saturating`.
     #[inline]
     pub fn to_i32(self) -> i32x4 {
-        unsafe {simd_cast(self)}
+        if cfg!(feature = "deterministic") {
+            self.to_i32_saturating()
+        } else {
+            unsafe {simd_cast(self)}
+        }
     }
     /// Convert each lane to an unsigned integer.
+    ///
+    /// NaN and out of range lanes give platform dependent results,
+    /// unless the `deterministic` feature is enabled, when this is
+    /// the same as `to_u32_saturating`.
     #[inline]
     pub fn to_u32(self) -> u32x4 {
-        unsafe {simd_cast(self)}
+        if cfg!(feature = "deterministic") {
+            self.to_u32_saturating()
+        } else {
+            unsafe {simd_cast(self)}
+        }
     }
 }
 
@@ -428,6 +1496,690 @@ neg_impls! {
     0.0,
     f32x4,
 }
+macro_rules! fma_impls {
+    ($($ty: ident: $mul_add: ident, $mul_sub: ident, $neg_mul_add: ident;)*) => {
+        $(impl $ty {
+            /// Compute `self * a + b` with only one rounding error.
+            ///
+            /// This is equivalent to the following, but is more
+            /// accurate, and is faster when the CPU supports fused
+            /// multiply-add (FMA3 on x86, VFPv4 on ARM):
+            ///
+            /// ```rust,ignore
+            /// self * a + b
+            /// ```
+            #[inline]
+            pub fn mul_add(self, a: Self, b: Self) -> Self {
+                ::common::fma::$mul_add(self, a, b)
+            }
+            /// Compute `self * a - b` with only one rounding error.
+            ///
+            /// This is equivalent to the following, but is more
+            /// accurate, and is faster when the CPU supports fused
+            /// multiply-add (FMA3 on x86, VFPv4 on ARM):
+            ///
+            /// ```rust,ignore
+            /// self * a - b
+            /// ```
+            #[inline]
+            pub fn mul_sub(self, a: Self, b: Self) -> Self {
+                ::common::fma::$mul_sub(self, a, b)
+            }
+            /// Compute `-(self * a) + b` with only one rounding error.
+            ///
+            /// This is equivalent to the following, but is more
+            /// accurate, and is faster when the CPU supports fused
+            /// multiply-add (FMA3 on x86, VFPv4 on ARM):
+            ///
+            /// ```rust,ignore
+            /// b - self * a
+            /// ```
+            #[inline]
+            pub fn neg_mul_add(self, a: Self, b: Self) -> Self {
+                ::common::fma::$neg_mul_add(self, a, b)
+            }
+        })*
+    }
+}
+fma_impls! {
+    f32x4: f32x4_mul_add, f32x4_mul_sub, f32x4_neg_mul_add;
+}
+
+// Check `mul_add`, `mul_sub` and `neg_mul_add` against the scalar
+// `mul_add`, which is correctly rounded.
+#[cfg(test)]
+macro_rules! check_fma {
+    ($ty: ident, $elem: ident, $length: expr) => {{
+        // `a * a` is `1 + 2 * ulp + ulp^2`, and the last term is lost
+        // when the product is rounded on its own
+        let ulp = ::std::$elem::EPSILON;
+        let a = $ty::splat(1.0 + ulp);
+        let c = $ty::splat(1.0 + 2.0 * ulp);
+        assert!((a * a - c).eq($ty::splat(0.0)).all());
+        assert!(a.mul_add(a, -c).eq($ty::splat(ulp * ulp)).all());
+        assert!(a.mul_sub(a, c).eq($ty::splat(ulp * ulp)).all());
+        assert!(a.neg_mul_add(a, c).eq($ty::splat(-ulp * ulp)).all());
+
+        let xs: Vec<$elem> = (0..64).map(|i| (i as $elem - 31.5) * 0.37).collect();
+        for i in 0..xs.len() - 3 * $length {
+            let x = $ty::load(&xs, i);
+            let y = $ty::load(&xs, i + $length);
+            let z = $ty::load(&xs, i + 2 * $length);
+            let (add, sub) = (x.mul_add(y, z), x.mul_sub(y, z));
+            let neg = x.neg_mul_add(y, z);
+            for j in 0..$length {
+                let (x, y, z) = (x.extract(j), y.extract(j), z.extract(j));
+                assert_eq!(add.extract(j), x.mul_add(y, z));
+                assert_eq!(sub.extract(j), x.mul_add(y, -z));
+                assert_eq!(neg.extract(j), (-x).mul_add(y, z));
+            }
+        }
+    }}
+}
+
+#[cfg(test)]
+mod fma_tests {
+    use super::*;
+
+    #[test]
+    fn f32x4_fma() {
+        check_fma!(f32x4, f32, 4);
+    }
+}
+
+macro_rules! float_bit_impls {
+    ($($ty: ident: $bits: ident;)*) => {
+        $(impl $ty {
+            /// Reinterpret the bits of each lane as an unsigned
+            /// integer.
+            #[inline]
+            pub fn to_bits(self) -> $bits {
+                bitcast(self)
+            }
+            /// Reinterpret the bits of each lane of `x` as a float.
+            #[inline]
+            pub fn from_bits(x: $bits) -> Self {
+                bitcast(x)
+            }
+            /// Compute the absolute value of each lane, by clearing
+            /// the sign bit.
+            #[inline]
+            pub fn abs(self) -> Self {
+                self & !$ty::splat(-0.0)
+            }
+            /// Compute `-self.abs()`, by setting the sign bit.
+            #[inline]
+            pub fn neg_abs(self) -> Self {
+                self | $ty::splat(-0.0)
+            }
+            /// Return each lane of `self` with the sign bit of the
+            /// corresponding lane of `sign`.
+            ///
+            /// This is equivalent to the following, but is possibly
+            /// more efficient:
+            ///
+            /// ```rust,ignore
+            /// T::new(self.extract(0).copysign(sign.extract(0)),
+            ///        self.extract(1).copysign(sign.extract(1)),
+            ///        ...)
+            /// ```
+            #[inline]
+            pub fn copysign(self, sign: Self) -> Self {
+                let mask = $ty::splat(-0.0);
+                (self & !mask) | (sign & mask)
+            }
+            /// Compute `1.0` for lanes with the sign bit clear
+            /// (including `+0.0` and `+inf`), `-1.0` for lanes with
+            /// it set, and NaN for NaN lanes, like `f32::signum`.
+            #[inline]
+            pub fn signum(self) -> Self {
+                let one = $ty::splat(1.0).copysign(self);
+                self.ne(self).select(self, one)
+            }
+        }
+          impl ops::BitAnd for $ty {
+              type Output = Self;
+              #[inline]
+              fn bitand(self, x: Self) -> Self {
+                  $ty::from_bits(self.to_bits() & x.to_bits())
+              }
+          }
+          impl ops::BitOr for $ty {
+              type Output = Self;
+              #[inline]
+              fn bitor(self, x: Self) -> Self {
+                  $ty::from_bits(self.to_bits() | x.to_bits())
+              }
+          }
+          impl ops::BitXor for $ty {
+              type Output = Self;
+              #[inline]
+              fn bitxor(self, x: Self) -> Self {
+                  $ty::from_bits(self.to_bits() ^ x.to_bits())
+              }
+          }
+          impl ops::Not for $ty {
+              type Output = Self;
+              #[inline]
+              fn not(self) -> Self {
+                  $ty::from_bits(!self.to_bits())
+              }
+          })*
+    }
+}
+float_bit_impls! {
+    f32x4: u32x4;
+}
+
+// Check the sign manipulation against the scalar functions, lane by
+// lane and bit for bit, over values of both signs including zeros,
+// infinities and NaN.
+#[cfg(test)]
+macro_rules! check_float_bits {
+    ($ty: ident, $elem: ident, $bits: ident, $length: expr) => {{
+        use std::mem::transmute;
+        let bits = |x: $elem| -> $bits { unsafe { transmute(x) } };
+        let nan = ::std::$elem::NAN;
+        let inf = ::std::$elem::INFINITY;
+        let xs: [$elem; 10] = [0.0, -0.0, 1.0, -1.5, 3.25e-3, -7.0e12,
+                               inf, -inf, nan, -nan];
+        for i in 0..xs.len() {
+            for j in 0..xs.len() {
+                let x = $ty::splat(xs[i]).replace(0, xs[j]);
+                let s = $ty::splat(xs[j]).replace($length - 1, xs[i]);
+                assert!($ty::from_bits(x.to_bits()).to_bits().eq(x.to_bits()).all());
+                let (abs, neg_abs) = (x.abs(), x.neg_abs());
+                let (copysign, signum) = (x.copysign(s), x.signum());
+                let (and, or, xor, not) = (x & s, x | s, x ^ s, !x);
+                for k in 0..$length {
+                    let (a, b) = (x.extract(k), s.extract(k));
+                    assert_eq!(x.to_bits().extract(k), bits(a));
+                    assert_eq!(abs.to_bits().extract(k), bits(a.abs()));
+                    assert_eq!(neg_abs.to_bits().extract(k), bits(-a.abs()));
+                    let expected = if b.is_sign_negative() { -a.abs() } else { a.abs() };
+                    assert_eq!(copysign.to_bits().extract(k), bits(expected));
+                    if a.is_nan() {
+                        assert!(signum.extract(k).is_nan());
+                    } else {
+                        assert_eq!(signum.to_bits().extract(k), bits(a.signum()));
+                    }
+                    assert_eq!(and.to_bits().extract(k), bits(a) & bits(b));
+                    assert_eq!(or.to_bits().extract(k), bits(a) | bits(b));
+                    assert_eq!(xor.to_bits().extract(k), bits(a) ^ bits(b));
+                    assert_eq!(not.to_bits().extract(k), !bits(a));
+                }
+            }
+        }
+    }}
+}
+
+#[cfg(test)]
+mod float_bit_tests {
+    use super::*;
+
+    #[test]
+    fn f32x4_float_bits() {
+        check_float_bits!(f32x4, f32, u32, 4);
+    }
+}
+
+macro_rules! float_class_impls {
+    ($($ty: ident: $elem: ident, $bool: ident;)*) => {
+        $(impl $ty {
+            /// Check which lanes are NaN.
+            #[inline]
+            pub fn is_nan(self) -> $bool {
+                self.ne(self)
+            }
+            /// Check which lanes are infinite.
+            #[inline]
+            pub fn is_infinite(self) -> $bool {
+                self.abs().eq($ty::splat(::std::$elem::INFINITY))
+            }
+            /// Check which lanes are neither infinite nor NaN.
+            #[inline]
+            pub fn is_finite(self) -> $bool {
+                self.abs().lt($ty::splat(::std::$elem::INFINITY))
+            }
+            /// Check which lanes are neither zero, infinite, subnormal
+            /// nor NaN.
+            #[inline]
+            pub fn is_normal(self) -> $bool {
+                let x = self.abs();
+                x.ge($ty::splat(::std::$elem::MIN_POSITIVE)) &
+                    x.lt($ty::splat(::std::$elem::INFINITY))
+            }
+            /// Check which lanes are subnormal.
+            #[inline]
+            pub fn is_subnormal(self) -> $bool {
+                let x = self.abs();
+                x.lt($ty::splat(::std::$elem::MIN_POSITIVE)) & x.ne($ty::splat(0.0))
+            }
+            /// Check which lanes have a negative sign bit, including
+            /// `-0.0` and NaNs with a negative sign bit.
+            #[inline]
+            pub fn is_sign_negative(self) -> $bool {
+                $ty::splat(1.0).copysign(self).lt($ty::splat(0.0))
+            }
+            /// Check which lanes have a positive sign bit, including
+            /// `+0.0` and NaNs with a positive sign bit.
+            #[inline]
+            pub fn is_sign_positive(self) -> $bool {
+                !self.is_sign_negative()
+            }
+        })*
+    }
+}
+float_class_impls! {
+    f32x4: f32, bool32ix4;
+}
+
+// Check the classification against the scalar predicates, on zeros,
+// subnormals, normals, infinities and NaNs of both signs.
+#[cfg(test)]
+macro_rules! check_float_class {
+    ($ty: ident, $elem: ident, $length: expr) => {{
+        use std::num::FpCategory;
+        use std::$elem::{MIN_POSITIVE, MAX, INFINITY, NAN};
+        let xs = [0.0, -0.0, MIN_POSITIVE / 2.0, -MIN_POSITIVE / 2.0, MIN_POSITIVE,
+                  -MIN_POSITIVE, 1.0, -2.5, MAX, -MAX, INFINITY, -INFINITY, NAN, -NAN];
+        for i in 0..xs.len() - $length {
+            let x = $ty::load(&xs, i);
+            let (nan, infinite, finite) = (x.is_nan(), x.is_infinite(), x.is_finite());
+            let (normal, subnormal) = (x.is_normal(), x.is_subnormal());
+            let (negative, positive) = (x.is_sign_negative(), x.is_sign_positive());
+            for k in 0..$length {
+                let a = x.extract(k);
+                assert_eq!(nan.extract(k), a.is_nan(), "{}", a);
+                assert_eq!(infinite.extract(k), a.is_infinite(), "{}", a);
+                assert_eq!(finite.extract(k), a.is_finite(), "{}", a);
+                assert_eq!(normal.extract(k), a.is_normal(), "{}", a);
+                assert_eq!(subnormal.extract(k), a.classify() == FpCategory::Subnormal, "{}", a);
+                assert_eq!(negative.extract(k), a.is_sign_negative(), "{}", a);
+                assert_eq!(positive.extract(k), a.is_sign_positive(), "{}", a);
+            }
+        }
+    }}
+}
+
+#[cfg(test)]
+mod float_class_tests {
+    use super::*;
+
+    #[test]
+    fn f32x4_float_class() {
+        check_float_class!(f32x4, f32, 4);
+    }
+}
+
+// IEEE 754's `minNum` and `maxNum`, built from comparisons, for the
+// backends whose minimum and maximum instructions disagree with them
+// about NaNs and signed zeros (e.g. `minps` returns its second operand
+// if either is NaN, while NEON's `vminq_f32` returns NaN).
+macro_rules! min_num_lanes {
+    ($x: expr, $y: expr) => {{
+        let (x, y) = ($x, $y);
+        let r = x.lt(y).select(x, y);
+        // equal lanes may be zeros of different signs
+        let r = x.eq(y).select(x | y, r);
+        let r = x.is_nan().select(y, r);
+        y.is_nan().select(x, r)
+    }}
+}
+macro_rules! max_num_lanes {
+    ($x: expr, $y: expr) => {{
+        let (x, y) = ($x, $y);
+        let r = x.gt(y).select(x, y);
+        // equal lanes may be zeros of different signs
+        let r = x.eq(y).select(x & y, r);
+        let r = x.is_nan().select(y, r);
+        y.is_nan().select(x, r)
+    }}
+}
+
+macro_rules! min_max_impls {
+    ($($ty: ident: $min_num: ident, $max_num: ident;)*) => {
+        $(impl $ty {
+            /// Compute the lane-wise minimum of `self` and `other`.
+            ///
+            /// This is the same as `min_num`: NaN lanes are ignored,
+            /// like `f32::min`. The result is the same on every
+            /// platform.
+            #[inline]
+            pub fn min(self, other: Self) -> Self {
+                self.min_num(other)
+            }
+            /// Compute the lane-wise maximum of `self` and `other`.
+            ///
+            /// This is the same as `max_num`: NaN lanes are ignored,
+            /// like `f32::max`. The result is the same on every
+            /// platform.
+            #[inline]
+            pub fn max(self, other: Self) -> Self {
+                self.max_num(other)
+            }
+            /// Compute the lane-wise minimum of `self` and `other`,
+            /// ignoring NaNs, like IEEE 754's `minNum`.
+            ///
+            /// If one lane is NaN the other is returned, and the
+            /// result is NaN only if both are. `-0.0` is treated as
+            /// less than `+0.0`.
+            #[inline]
+            pub fn min_num(self, other: Self) -> Self {
+                common::$min_num(self, other)
+            }
+            /// Compute the lane-wise maximum of `self` and `other`,
+            /// ignoring NaNs, like IEEE 754's `maxNum`.
+            ///
+            /// If one lane is NaN the other is returned, and the
+            /// result is NaN only if both are. `+0.0` is treated as
+            /// greater than `-0.0`.
+            #[inline]
+            pub fn max_num(self, other: Self) -> Self {
+                common::$max_num(self, other)
+            }
+            /// Compute the lane-wise minimum of `self` and `other`,
+            /// propagating NaNs, like IEEE 754's `minimum`.
+            ///
+            /// The result is NaN if either lane is. `-0.0` is treated
+            /// as less than `+0.0`.
+            #[inline]
+            pub fn minimum(self, other: Self) -> Self {
+                let r = self.lt(other).select(self, other);
+                let r = self.eq(other).select(self | other, r);
+                (self.is_nan() | other.is_nan()).select(self + other, r)
+            }
+            /// Compute the lane-wise maximum of `self` and `other`,
+            /// propagating NaNs, like IEEE 754's `maximum`.
+            ///
+            /// The result is NaN if either lane is. `+0.0` is treated
+            /// as greater than `-0.0`.
+            #[inline]
+            pub fn maximum(self, other: Self) -> Self {
+                let r = self.gt(other).select(self, other);
+                let r = self.eq(other).select(self & other, r);
+                (self.is_nan() | other.is_nan()).select(self + other, r)
+            }
+        })*
+    }
+}
+min_max_impls! {
+    f32x4: f32x4_min_num, f32x4_max_num;
+}
+
+// Check the four kinds of minimum and maximum against their
+// definitions, lane by lane, on NaNs and zeros of both signs as well
+// as ordinary values.
+#[cfg(test)]
+macro_rules! check_min_max {
+    ($ty: ident, $elem: ident, $length: expr) => {{
+        use std::$elem::{INFINITY, NAN};
+        let xs = [0.0, -0.0, 1.0, -1.0, 2.5, INFINITY, -INFINITY, NAN, -NAN];
+        // what `a.min(b)` would be if `-0.0 < 0.0`
+        let lesser = |a: $elem, b: $elem| {
+            if a < b || (a == b && a.is_sign_negative()) { a } else { b }
+        };
+        let greater = |a: $elem, b: $elem| {
+            if a > b || (a == b && a.is_sign_positive()) { a } else { b }
+        };
+        let same = |x: $elem, y: $elem| x == y && x.is_sign_negative() == y.is_sign_negative();
+        for i in 0..xs.len() {
+            for j in 0..xs.len() {
+                let x = $ty::splat(xs[i]).replace($length - 1, xs[j]);
+                let y = $ty::splat(xs[j]).replace($length - 1, xs[i]);
+                let (min, max) = (x.min(y), x.max(y));
+                let (min_num, max_num) = (x.min_num(y), x.max_num(y));
+                let (minimum, maximum) = (x.minimum(y), x.maximum(y));
+                for k in 0..$length {
+                    let (a, b) = (x.extract(k), y.extract(k));
+                    let either_nan = a.is_nan() || b.is_nan();
+                    let (want_min, want_max) = if a.is_nan() {
+                        (b, b)
+                    } else if b.is_nan() {
+                        (a, a)
+                    } else {
+                        (lesser(a, b), greater(a, b))
+                    };
+                    for &(got, want) in &[(min_num.extract(k), want_min),
+                                          (max_num.extract(k), want_max),
+                                          (min.extract(k), want_min),
+                                          (max.extract(k), want_max)] {
+                        assert!(same(got, want) || (got.is_nan() && want.is_nan()),
+                                "{} {} gave {}, not {}", a, b, got, want);
+                    }
+                    if either_nan {
+                        assert!(minimum.extract(k).is_nan(), "minimum({}, {})", a, b);
+                        assert!(maximum.extract(k).is_nan(), "maximum({}, {})", a, b);
+                    } else {
+                        assert!(same(minimum.extract(k), want_min), "minimum({}, {})", a, b);
+                        assert!(same(maximum.extract(k), want_max), "maximum({}, {})", a, b);
+                    }
+                }
+            }
+        }
+    }}
+}
+
+#[cfg(test)]
+mod min_max_tests {
+    use super::*;
+
+    #[test]
+    fn f32x4_min_max() {
+        check_min_max!(f32x4, f32, 4);
+    }
+}
+
+// If `y` has relative error `e`, a Newton-Raphson step for the
+// reciprocal leaves an error of `e^2`, and one for the reciprocal
+// square root an error of about `1.5 * e^2`, so each step doubles the
+// number of correct bits, less one for the latter, until rounding
+// errors take over at about 22 bits for `f32` and 51 for `f64`.
+//
+// The square root step multiplies by `self` before the halving, since
+// halving a subnormal `self` first would round away its low bit.
+macro_rules! refine_impls {
+    ($($ty: ident,)*) => {
+        $(impl $ty {
+            /// Compute `1.0 / self.sqrt()` by refining `approx_rsqrt`
+            /// with one Newton-Raphson step.
+            ///
+            /// This gives about 22 bits of precision for `f32` lanes on
+            /// x86 but only 13 on ARM, and 34 bits for `f64` lanes.
+            /// Lanes that are zero, infinite or NaN give the same
+            /// result as `approx_rsqrt`.
+            #[inline]
+            pub fn rsqrt_precise1(self) -> Self {
+                let y0 = self.approx_rsqrt();
+                self.refined(y0, self.rsqrt_step(y0))
+            }
+            /// Compute `1.0 / self.sqrt()` by refining `approx_rsqrt`
+            /// with two Newton-Raphson steps.
+            ///
+            /// This gives about 22 bits of precision for `f32` lanes
+            /// and 51 for `f64` lanes on every platform; use
+            /// `self.sqrt()` and a division for a correctly rounded
+            /// result. Lanes that are zero, infinite or NaN give the
+            /// same result as `approx_rsqrt`.
+            #[inline]
+            pub fn rsqrt_precise2(self) -> Self {
+                let y0 = self.approx_rsqrt();
+                self.refined(y0, self.rsqrt_step(self.rsqrt_step(y0)))
+            }
+            /// Compute `1.0 / self` by refining `approx_reciprocal`
+            /// with one Newton-Raphson step.
+            ///
+            /// This gives about 22 bits of precision for `f32` lanes on
+            /// x86 but only 14 on ARM, and 34 bits for `f64` lanes.
+            /// Lanes that are zero, infinite or NaN give the same
+            /// result as `approx_reciprocal`.
+            #[inline]
+            pub fn recip_precise1(self) -> Self {
+                let y0 = self.approx_reciprocal();
+                self.refined(y0, self.recip_step(y0))
+            }
+            /// Compute `1.0 / self` by refining `approx_reciprocal`
+            /// with two Newton-Raphson steps.
+            ///
+            /// This gives about 22 bits of precision for `f32` lanes
+            /// and 51 for `f64` lanes on every platform; use a division
+            /// for a correctly rounded result. Lanes that are zero,
+            /// infinite or NaN give the same result as
+            /// `approx_reciprocal`.
+            #[inline]
+            pub fn recip_precise2(self) -> Self {
+                let y0 = self.approx_reciprocal();
+                self.refined(y0, self.recip_step(self.recip_step(y0)))
+            }
+
+            #[inline]
+            fn rsqrt_step(self, y: Self) -> Self {
+                y * ($ty::splat(1.5) - $ty::splat(0.5) * (self * y * y))
+            }
+            #[inline]
+            fn recip_step(self, y: Self) -> Self {
+                y * ($ty::splat(2.0) - self * y)
+            }
+            // the steps would turn infinite and zero estimates into NaN
+            #[inline]
+            fn refined(self, y0: Self, y: Self) -> Self {
+                (y0.is_finite() & y0.ne($ty::splat(0.0))).select(y, y0)
+            }
+        })*
+    }
+}
+refine_impls! {
+    f32x4,
+}
+
+// Check that `$method` has the precision that `$steps` Newton-Raphson
+// steps give from `$approx` bits: each doubles the bits (or, for the
+// square root, doubles them less one, as `$step` says) up to the
+// rounding limit `$cap`. The inputs are `2^k * (1 + j / 16)` for `k`
+// in `$exps`, with the power split so that it doesn't overflow before
+// the product goes subnormal; x86's `f32` estimates treat subnormals
+// as zero, so the `f32` tests leave them out.
+#[cfg(test)]
+macro_rules! check_refine {
+    ($ty: ident, $elem: ident, $length: expr, $method: ident, $exps: expr,
+     $approx: expr, $steps: expr, $step: expr, $cap: expr, $exact: expr) => {{
+        let step = $step;
+        let bits: u32 = (0..$steps).fold($approx, |b, _| ::std::cmp::min(step(b), $cap));
+        let tol = 0.5f64.powi(bits as i32);
+        let exact = $exact;
+        for k in $exps {
+            for j in 0..16 {
+                let x = 2.0f64.powi(k / 2) * 2.0f64.powi(k - k / 2) * (1.0 + j as f64 / 16.0);
+                let x = x as $elem;
+                let y = exact(x as f64);
+                let got = $ty::splat(x).$method();
+                for i in 0..$length {
+                    let err = ((got.extract(i) as f64 - y) / y).abs();
+                    assert!(err <= tol, "{}({:e}) = {:e}, wanted {} bits of {:e}",
+                            stringify!($method), x, got.extract(i), bits, y);
+                }
+            }
+        }
+    }}
+}
+
+// The precision of the `f32x4` estimates, for the tests of `f32x8`.
+#[cfg(test)]
+pub const F32X4_APPROX_BITS: u32 = common::F32X4_APPROX_BITS;
+
+#[cfg(test)]
+mod refine_tests {
+    use super::*;
+
+    #[test]
+    fn f32x4_rsqrt_precise1() {
+        check_refine!(f32x4, f32, 4, rsqrt_precise1, -126..128,
+                      F32X4_APPROX_BITS, 1, |b| 2 * b - 1, 22, |x: f64| 1.0 / x.sqrt());
+    }
+    #[test]
+    fn f32x4_rsqrt_precise2() {
+        check_refine!(f32x4, f32, 4, rsqrt_precise2, -126..128,
+                      F32X4_APPROX_BITS, 2, |b| 2 * b - 1, 22, |x: f64| 1.0 / x.sqrt());
+    }
+    #[test]
+    fn f32x4_recip_precise1() {
+        check_refine!(f32x4, f32, 4, recip_precise1, -126..126,
+                      F32X4_APPROX_BITS, 1, |b| 2 * b, 22, |x: f64| 1.0 / x);
+    }
+    #[test]
+    fn f32x4_recip_precise2() {
+        check_refine!(f32x4, f32, 4, recip_precise2, -126..126,
+                      F32X4_APPROX_BITS, 2, |b| 2 * b, 22, |x: f64| 1.0 / x);
+    }
+}
+
+// There's no estimate instruction for `f64` lanes before AVX-512, so
+// these start from the classic integer trick (a linear approximation
+// of the logarithm in the bit pattern, good to 4 or 5 bits) and take
+// two Newton-Raphson steps. Inputs the trick can't handle are scaled
+// into range first.
+macro_rules! f64_approx_impls {
+    ($($ty: ident: $uty: ident;)*) => {
+        $(impl $ty {
+            /// Compute an approximation to the reciprocal of the square
+            /// root of `self`, that is, `f64::splat(1.0) / self.sqrt()`.
+            ///
+            /// This has 17 bits of precision on every platform; see
+            /// `rsqrt_precise2` for more.
+            #[inline]
+            pub fn approx_rsqrt(self) -> Self {
+                const TWO_54: f64 = 18014398509481984.0;
+                const TWO_27: f64 = 134217728.0;
+                let zero = $ty::splat(0.0);
+                let inf = $ty::splat(::std::f64::INFINITY);
+
+                let tiny = self.lt($ty::splat(::std::f64::MIN_POSITIVE));
+                let x = tiny.select(self * $ty::splat(TWO_54), self);
+                let magic = $uty::splat(0x5fe6_eb50_c7b5_37a9);
+                let y = $ty::from_bits(magic - (x.to_bits() >> 1u32));
+                let half = $ty::splat(0.5) * x;
+                let y = y * ($ty::splat(1.5) - half * y * y);
+                let y = y * ($ty::splat(1.5) - half * y * y);
+                let y = tiny.select(y * $ty::splat(TWO_27), y);
+
+                let y = self.eq(zero).select(inf.copysign(self), y);
+                let y = self.eq(inf).select(zero, y);
+                self.lt(zero).select($ty::splat(::std::f64::NAN), y)
+            }
+            /// Compute an approximation to the reciprocal of `self`,
+            /// that is, `f64::splat(1.0) / self`.
+            ///
+            /// This has 17 bits of precision on every platform; see
+            /// `recip_precise2` for more.
+            #[inline]
+            pub fn approx_reciprocal(self) -> Self {
+                const TWO_54: f64 = 18014398509481984.0;
+                const TWO_M54: f64 = 5.551115123125783e-17;
+                const TWO_1000: f64 = 1.0715086071862673e301;
+                const TWO_M1000: f64 = 9.332636185032189e-302;
+                let zero = $ty::splat(0.0);
+                let inf = $ty::splat(::std::f64::INFINITY);
+
+                let a = self.abs();
+                let tiny = a.lt($ty::splat(TWO_M1000));
+                let huge = a.gt($ty::splat(TWO_1000));
+                let x = tiny.select(a * $ty::splat(TWO_54), a);
+                let x = huge.select(x * $ty::splat(TWO_M54), x);
+                let magic = $uty::splat(0x7fde_6238_22fc_16e6);
+                let y = $ty::from_bits(magic - x.to_bits());
+                let y = y * ($ty::splat(2.0) - x * y);
+                let y = y * ($ty::splat(2.0) - x * y);
+                let y = tiny.select(y * $ty::splat(TWO_54), y);
+                let y = huge.select(y * $ty::splat(TWO_M54), y);
+
+                let y = a.eq(zero).select(inf, y);
+                let y = a.eq(inf).select(zero, y);
+                y.copysign(self)
+            }
+        })*
+    }
+}
+
 macro_rules! not_impls {
     ($($ty: ident,)*) => {
         $(impl ops::Not for $ty {
@@ -474,16 +2226,600 @@ operators! {
 
     BitAnd (simd_and, bitand):
         i8x16, u8x16, i16x8, u16x8, i32x4, u32x4,
-        bool8ix16, bool16ix8, bool32ix4,
-        bool32fx4;
+        bool8ix16, bool16ix8, bool32ix4;
     BitOr (simd_or, bitor):
         i8x16, u8x16, i16x8, u16x8, i32x4, u32x4,
-        bool8ix16, bool16ix8, bool32ix4,
-        bool32fx4;
+        bool8ix16, bool16ix8, bool32ix4;
     BitXor (simd_xor, bitxor):
         i8x16, u8x16, i16x8, u16x8, i32x4, u32x4,
-        bool8ix16, bool16ix8, bool32ix4,
-        bool32fx4;
+        bool8ix16, bool16ix8, bool32ix4;
+}
+
+macro_rules! float_to_int {
+    ($($f: ident ($fint: ident, $exact: expr) {
+        $($i: ident, $ielem: ident, $ibool: ident:
+          $saturating: ident, $round: ident, $floor: ident, $try: ident,
+          $lower_cmp: ident $lower: expr, $upper: expr;)*
+    })*) => {
+        $(impl $f {
+            /// Round each lane toward zero.
+            #[inline]
+            fn trunc_lanes(self) -> Self {
+                // at least this big, floats are already integers
+                let small = self.abs().lt($f::splat($exact));
+                let x = small.select(self, $f::splat(0.0));
+                let t: $fint = unsafe {simd_cast(x)};
+                small.select(unsafe {simd_cast(t)}, self)
+            }
+            /// Round each lane toward negative infinity.
+            #[inline]
+            fn floor_lanes(self) -> Self {
+                let t = self.trunc_lanes();
+                t - t.gt(self).select($f::splat(1.0), $f::splat(0.0))
+            }
+            /// Round each lane to the nearest integer, with ties away
+            /// from zero.
+            #[inline]
+            fn round_lanes(self) -> Self {
+                let t = self.trunc_lanes();
+                let half = (self - t).abs().ge($f::splat(0.5));
+                t + half.select($f::splat(1.0).copysign(self), $f::splat(0.0))
+            }
+
+            $(
+                /// Convert each lane to an integer, rounding toward
+                /// zero, like `as`: NaN lanes become zero, and lanes
+                /// outside the range of the integer type become its
+                /// minimum or maximum.
+                #[inline]
+                pub fn $saturating(self) -> $i {
+                    let too_big = self.ge($f::splat($upper));
+                    let in_range = self.$lower_cmp($f::splat($lower)) & self.lt($f::splat($upper));
+                    let x = in_range.select(self, $f::splat(0.0));
+                    let r: $i = unsafe {simd_cast(x)};
+                    let r = in_range.select(r, $i::splat(::std::$ielem::MIN));
+                    let r = too_big.select($i::splat(::std::$ielem::MAX), r);
+                    self.ne(self).select($i::splat(0), r)
+                }
+                /// Convert each lane to an integer, rounding to the
+                /// nearest integer with ties away from zero (like
+                /// `f32::round`). NaN and out of range lanes saturate,
+                /// as for `as`.
+                #[inline]
+                pub fn $round(self) -> $i {
+                    self.round_lanes().$saturating()
+                }
+                /// Convert each lane to an integer, rounding toward
+                /// negative infinity. NaN and out of range lanes
+                /// saturate, as for `as`.
+                #[inline]
+                pub fn $floor(self) -> $i {
+                    self.floor_lanes().$saturating()
+                }
+                /// Convert each lane to an integer, rounding toward
+                /// zero and saturating, also returning a mask of the
+                /// lanes that were NaN or out of range.
+                #[inline]
+                pub fn $try(self) -> ($i, $ibool) {
+                    let in_range = self.$lower_cmp($f::splat($lower)) & self.lt($f::splat($upper));
+                    (self.$saturating(), !in_range)
+                }
+                )*
+        })*
+    }
+}
+float_to_int! {
+    f32x4 (i32x4, 8388608.0) {
+        i32x4, i32, bool32ix4:
+            to_i32_saturating, to_i32_round, to_i32_floor, try_to_i32,
+            ge -2147483648.0, 2147483648.0;
+        u32x4, u32, bool32ix4:
+            to_u32_saturating, to_u32_round, to_u32_floor, try_to_u32,
+            gt -1.0, 4294967296.0;
+    }
+}
+
+// Check the conversions against the scalar rounding functions, on
+// values around the limits of each integer type, with the saturation
+// worked out explicitly: NaN gives zero, and anything that truncates
+// to below the minimum or above the maximum gives that limit.
+#[cfg(test)]
+macro_rules! check_float_to_int {
+    ($f: ident, $felem: ident, $ielem: ident, $length: expr,
+     $saturating: ident, $round: ident, $floor: ident, $try: ident) => {{
+        let values = [0.0, -0.0, 0.4, 0.5, -0.5, 0.7, 1.5, -1.5, 2.5, -2.5, -2.7, -0.9, -1.0,
+                      1e10, -1e10, 1e30, -1e30, 8388607.5, 4503599627370495.5,
+                      2147483520.0, 2147483648.0, -2147483648.0, -2147483904.0,
+                      4294967040.0, 4294967296.0,
+                      9223372036854774784.0, 9223372036854775808.0,
+                      -9223372036854775808.0, -9223372036854777856.0,
+                      18446744073709549568.0, 18446744073709551616.0,
+                      ::std::f64::INFINITY, ::std::f64::NEG_INFINITY, ::std::f64::NAN];
+        let bits = 8 * ::std::mem::size_of::<$ielem>() as i32;
+        let min = $ielem::min_value();
+        let max = $ielem::max_value();
+        // the first value above `max`, which is a power of two
+        let upper = if min == 0 { 2.0f64.powi(bits) } else { 2.0f64.powi(bits - 1) };
+        let in_range = |y: f64| y.trunc() >= min as f64 && y.trunc() < upper;
+        let want = |y: f64| -> $ielem {
+            if y.is_nan() {
+                0
+            } else if y.trunc() < min as f64 {
+                min
+            } else if y.trunc() >= upper {
+                max
+            } else {
+                y.trunc() as $ielem
+            }
+        };
+        let xs: Vec<$felem> = values.iter().map(|&x| x as $felem).collect();
+        for i in 0..xs.len() - $length {
+            let x = $f::load(&xs, i);
+            let (saturating, round, floor) = (x.$saturating(), x.$round(), x.$floor());
+            let (tried, bad) = x.$try();
+            for k in 0..$length {
+                let y = x.extract(k) as f64;
+                assert_eq!(saturating.extract(k), want(y), "{}", y);
+                assert_eq!(round.extract(k), want(y.round()), "round {}", y);
+                assert_eq!(floor.extract(k), want(y.floor()), "floor {}", y);
+                assert_eq!(tried.extract(k), want(y), "{}", y);
+                assert_eq!(bad.extract(k), !in_range(y), "{}", y);
+            }
+        }
+    }}
+}
+
+#[cfg(test)]
+mod float_to_int_tests {
+    use super::*;
+
+    #[test]
+    fn f32x4_to_i32() {
+        check_float_to_int!(f32x4, f32, i32, 4,
+                            to_i32_saturating, to_i32_round, to_i32_floor, try_to_i32);
+    }
+    #[test]
+    fn f32x4_to_u32() {
+        check_float_to_int!(f32x4, f32, u32, 4,
+                            to_u32_saturating, to_u32_round, to_u32_floor, try_to_u32);
+    }
+}
+
+macro_rules! widening_mul_impls {
+    // via the vector with lanes twice as wide
+    ($($ty: ident: widen $wide: ident, $bits: expr;)*) => {
+        $(impl $ty {
+            /// Multiply each lane by the corresponding lane of `other`,
+            /// returning the low and the high halves of the
+            /// double-width products.
+            ///
+            /// The low half is the same as `self * other`.
+            #[inline]
+            pub fn widening_mul(self, other: Self) -> (Self, Self) {
+                unsafe {
+                    let x: $wide = simd_cast(self);
+                    let y: $wide = simd_cast(other);
+                    let p = x * y;
+                    (simd_cast(p), simd_cast(p >> ($bits as u32)))
+                }
+            }
+            /// Multiply each lane by the corresponding lane of `other`,
+            /// returning the high half of the double-width products.
+            #[inline]
+            pub fn mul_hi(self, other: Self) -> Self {
+                self.widening_mul(other).1
+            }
+        })*
+    };
+    // via the 128-bit halves
+    ($($ty: ident: halves $shuffle: ident [$($idx: expr),*];)*) => {
+        $(impl $ty {
+            /// Multiply each lane by the corresponding lane of `other`,
+            /// returning the low and the high halves of the
+            /// double-width products.
+            ///
+            /// The low half is the same as `self * other`.
+            #[inline]
+            pub fn widening_mul(self, other: Self) -> (Self, Self) {
+                let (lo0, hi0) = self.low().widening_mul(other.low());
+                let (lo1, hi1) = self.high().widening_mul(other.high());
+                unsafe {
+                    ($shuffle(lo0, lo1, [$($idx),*]), $shuffle(hi0, hi1, [$($idx),*]))
+                }
+            }
+            /// Multiply each lane by the corresponding lane of `other`,
+            /// returning the high half of the double-width products.
+            #[inline]
+            pub fn mul_hi(self, other: Self) -> Self {
+                self.widening_mul(other).1
+            }
+        })*
+    };
+    // 64-bit lanes, via 32-bit partial products
+    ($($ty: ident: split $u: ident, $signed: expr;)*) => {
+        $(impl $ty {
+            /// Multiply each lane by the corresponding lane of `other`,
+            /// returning the low and the high halves of the 128-bit
+            /// products.
+            ///
+            /// The low half is the same as `self * other`.
+            #[inline]
+            pub fn widening_mul(self, other: Self) -> (Self, Self) {
+                let x: $u = bitcast(self);
+                let y: $u = bitcast(other);
+                let mask = $u::splat(0xffff_ffff);
+                let (x0, x1) = (x & mask, x >> 32u32);
+                let (y0, y1) = (y & mask, y >> 32u32);
+                let p00 = x0 * y0;
+                let p01 = x0 * y1;
+                let p10 = x1 * y0;
+                let mid = (p00 >> 32u32) + (p01 & mask) + (p10 & mask);
+                let lo = (mid << 32u32) | (p00 & mask);
+                let hi = x1 * y1 + (p01 >> 32u32) + (p10 >> 32u32) + (mid >> 32u32);
+                let hi = if $signed {
+                    // the sign bit is worth -2^63, not 2^63
+                    let x_neg: $u = bitcast(self >> 63u32);
+                    let y_neg: $u = bitcast(other >> 63u32);
+                    hi - (x_neg & y) - (y_neg & x)
+                } else {
+                    hi
+                };
+                (bitcast(lo), bitcast(hi))
+            }
+            /// Multiply each lane by the corresponding lane of `other`,
+            /// returning the high half of the 128-bit products.
+            #[inline]
+            pub fn mul_hi(self, other: Self) -> Self {
+                self.widening_mul(other).1
+            }
+        })*
+    };
+}
+widening_mul_impls! {
+    u8x16: widen u16x16, 8;
+    i8x16: widen i16x16, 8;
+    u16x8: widen u32x8, 16;
+    i16x8: widen i32x8, 16;
+    u32x4: widen u64x4, 32;
+    i32x4: widen i64x4, 32;
+}
+
+// Edge cases and some pseudo-random values, cast to each lane type.
+#[cfg(test)]
+macro_rules! mul_values {
+    ($elem: ident) => {{
+        let mut xs: Vec<u64> = vec![0, 1, 2, 3, !0, !0 - 1, 1 << 7, 1 << 15, 1 << 31, 1 << 63];
+        let below: Vec<u64> = xs.iter().map(|&x| x.wrapping_sub(1)).collect();
+        xs.extend(below);
+        let mut r = 0x0123_4567_89ab_cdefu64;
+        for _ in 0..16 {
+            r = r.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+            xs.push(r);
+        }
+        xs.iter().map(|&x| x as $elem).collect::<Vec<$elem>>()
+    }}
+}
+
+// Check `widening_mul` and `mul_hi` against the products of the lanes
+// cast to `$wide`, which is twice as wide.
+#[cfg(test)]
+macro_rules! check_widening_mul {
+    ($ty: ident, $elem: ident, $wide: ident, $length: expr) => {{
+        let bits = 8 * ::std::mem::size_of::<$elem>() as u32;
+        let xs = mul_values!($elem);
+        for i in 0..xs.len() - $length {
+            for j in 0..xs.len() - $length {
+                let (x, y) = ($ty::load(&xs, i), $ty::load(&xs, j));
+                let (lo, hi) = x.widening_mul(y);
+                assert!(hi.eq(x.mul_hi(y)).all());
+                for k in 0..$length {
+                    let (a, b) = (x.extract(k), y.extract(k));
+                    let p = a as $wide * b as $wide;
+                    assert_eq!(lo.extract(k), p as $elem, "{} * {}", a, b);
+                    assert_eq!(hi.extract(k), (p >> bits) as $elem, "{} * {}", a, b);
+                }
+            }
+        }
+    }}
+}
+
+// Check the 64-bit `widening_mul` against 128-bit products computed
+// by shifting and adding, rather than by the partial products the
+// vectors use. `$reference` is `mul_u64` or `mul_i64`.
+#[cfg(test)]
+macro_rules! check_widening_mul_64 {
+    ($ty: ident, $elem: ident, $reference: ident, $length: expr) => {{
+        // (low, high) halves
+        #[allow(dead_code)]
+        fn mul_u64(x: u64, y: u64) -> (u64, u64) {
+            let (mut lo, mut hi) = (0u64, 0u64);
+            for i in 0..64 {
+                if y >> i & 1 == 1 {
+                    let (l, carry) = lo.overflowing_add(x << i);
+                    let h = if i == 0 { 0 } else { x >> (64 - i) };
+                    lo = l;
+                    hi = hi.wrapping_add(h).wrapping_add(carry as u64);
+                }
+            }
+            (lo, hi)
+        }
+        #[allow(dead_code)]
+        fn mul_i64(x: i64, y: i64) -> (u64, u64) {
+            let abs = |x: i64| if x < 0 { 0u64.wrapping_sub(x as u64) } else { x as u64 };
+            let (lo, hi) = mul_u64(abs(x), abs(y));
+            if (x < 0) != (y < 0) {
+                (0u64.wrapping_sub(lo), (!hi).wrapping_add(if lo == 0 { 1 } else { 0 }))
+            } else {
+                (lo, hi)
+            }
+        }
+        let xs = mul_values!($elem);
+        for i in 0..xs.len() - $length {
+            for j in 0..xs.len() - $length {
+                let (x, y) = ($ty::load(&xs, i), $ty::load(&xs, j));
+                let (lo, hi) = x.widening_mul(y);
+                assert!(hi.eq(x.mul_hi(y)).all());
+                for k in 0..$length {
+                    let (a, b) = (x.extract(k), y.extract(k));
+                    let (want_lo, want_hi) = $reference(a, b);
+                    assert_eq!(lo.extract(k) as u64, want_lo, "{} * {}", a, b);
+                    assert_eq!(hi.extract(k) as u64, want_hi, "{} * {}", a, b);
+                }
+            }
+        }
+    }}
+}
+
+#[cfg(test)]
+mod widening_mul_tests {
+    use super::*;
+
+    #[test]
+    fn u8x16_widening_mul() {
+        check_widening_mul!(u8x16, u8, u16, 16);
+    }
+    #[test]
+    fn i8x16_widening_mul() {
+        check_widening_mul!(i8x16, i8, i16, 16);
+    }
+    #[test]
+    fn u16x8_widening_mul() {
+        check_widening_mul!(u16x8, u16, u32, 8);
+    }
+    #[test]
+    fn i16x8_widening_mul() {
+        check_widening_mul!(i16x8, i16, i32, 8);
+    }
+    #[test]
+    fn u32x4_widening_mul() {
+        check_widening_mul!(u32x4, u32, u64, 4);
+    }
+    #[test]
+    fn i32x4_widening_mul() {
+        check_widening_mul!(i32x4, i32, i64, 4);
+    }
+}
+
+// there's no vector integer division on any of the supported CPUs,
+// so this is lane-wise; it also gets the panics of the scalar
+// operators for free.
+macro_rules! int_div_impls {
+    ($($ty: ident, $length: expr;)*) => {
+        $(
+            /// Divide each lane by the corresponding lane of the
+            /// right-hand side.
+            ///
+            /// # Panics
+            ///
+            /// Like scalar division, this panics if any lane of the
+            /// divisor is zero, or if any lane overflows (`MIN / -1`).
+            impl ops::Div for $ty {
+                type Output = Self;
+                #[inline]
+                fn div(self, x: Self) -> Self {
+                    let mut ret = self;
+                    for i in 0..$length {
+                        ret = ret.replace(i, self.extract(i) / x.extract(i));
+                    }
+                    ret
+                }
+            }
+            /// Compute the remainder of dividing each lane by the
+            /// corresponding lane of the right-hand side.
+            ///
+            /// # Panics
+            ///
+            /// Like scalar remainder, this panics if any lane of the
+            /// divisor is zero, or if any lane overflows (`MIN % -1`).
+            impl ops::Rem for $ty {
+                type Output = Self;
+                #[inline]
+                fn rem(self, x: Self) -> Self {
+                    let mut ret = self;
+                    for i in 0..$length {
+                        ret = ret.replace(i, self.extract(i) % x.extract(i));
+                    }
+                    ret
+                }
+            }
+            )*
+    }
+}
+int_div_impls! {
+    i8x16, 16;
+    u8x16, 16;
+    i16x8, 8;
+    u16x8, 8;
+    i32x4, 4;
+    u32x4, 4;
+}
+
+#[cfg(test)]
+mod int_div_tests {
+    use super::*;
+
+    #[test]
+    fn i32x4_div_rem() {
+        let xs = [7, -7, i32::max_value(), i32::min_value()];
+        let ys = [2, -3, 1000, 7];
+        let (x, y) = (i32x4::load(&xs, 0), i32x4::load(&ys, 0));
+        let (q, r) = (x / i32x4::splat(3), x % y);
+        for i in 0..4 {
+            assert_eq!(q.extract(i), xs[i as usize] / 3);
+            assert_eq!(r.extract(i), xs[i as usize] % ys[i as usize]);
+        }
+    }
+    #[test]
+    #[should_panic]
+    fn u8x16_div_zero() {
+        u8x16::splat(1) / u8x16::splat(1).replace(15, 0);
+    }
+}
+
+// The reference, scalar and assigning forms of the binary operators,
+// in terms of the by-value impls. A scalar on either side is splatted
+// to every lane.
+macro_rules! operator_forms {
+    ($($trayt: ident, $assign: ident ($method: ident, $assign_method: ident):
+       $($ty: ident: $elem: ty),*;)*) => {
+        $(
+            $(impl<'a> ops::$trayt<&'a $ty> for $ty {
+                type Output = $ty;
+                #[inline]
+                fn $method(self, x: &'a $ty) -> $ty {
+                    ops::$trayt::$method(self, *x)
+                }
+            }
+              impl<'a> ops::$trayt<$ty> for &'a $ty {
+                  type Output = $ty;
+                  #[inline]
+                  fn $method(self, x: $ty) -> $ty {
+                      ops::$trayt::$method(*self, x)
+                  }
+              }
+              impl<'a, 'b> ops::$trayt<&'a $ty> for &'b $ty {
+                  type Output = $ty;
+                  #[inline]
+                  fn $method(self, x: &'a $ty) -> $ty {
+                      ops::$trayt::$method(*self, *x)
+                  }
+              }
+              impl ops::$trayt<$elem> for $ty {
+                  type Output = $ty;
+                  #[inline]
+                  fn $method(self, x: $elem) -> $ty {
+                      ops::$trayt::$method(self, $ty::splat(x))
+                  }
+              }
+              impl ops::$trayt<$ty> for $elem {
+                  type Output = $ty;
+                  #[inline]
+                  fn $method(self, x: $ty) -> $ty {
+                      ops::$trayt::$method($ty::splat(self), x)
+                  }
+              }
+              impl ops::$assign for $ty {
+                  #[inline]
+                  fn $assign_method(&mut self, x: $ty) {
+                      *self = ops::$trayt::$method(*self, x);
+                  }
+              }
+              impl<'a> ops::$assign<&'a $ty> for $ty {
+                  #[inline]
+                  fn $assign_method(&mut self, x: &'a $ty) {
+                      *self = ops::$trayt::$method(*self, *x);
+                  }
+              }
+              impl ops::$assign<$elem> for $ty {
+                  #[inline]
+                  fn $assign_method(&mut self, x: $elem) {
+                      *self = ops::$trayt::$method(*self, $ty::splat(x));
+                  }
+              })*
+                )*
+    }
+}
+operator_forms! {
+    Add, AddAssign (add, add_assign):
+        i8x16: i8, u8x16: u8, i16x8: i16, u16x8: u16, i32x4: i32, u32x4: u32, f32x4: f32;
+    Sub, SubAssign (sub, sub_assign):
+        i8x16: i8, u8x16: u8, i16x8: i16, u16x8: u16, i32x4: i32, u32x4: u32, f32x4: f32;
+    Mul, MulAssign (mul, mul_assign):
+        i8x16: i8, u8x16: u8, i16x8: i16, u16x8: u16, i32x4: i32, u32x4: u32, f32x4: f32;
+    Div, DivAssign (div, div_assign):
+        i8x16: i8, u8x16: u8, i16x8: i16, u16x8: u16, i32x4: i32, u32x4: u32, f32x4: f32;
+    Rem, RemAssign (rem, rem_assign):
+        i8x16: i8, u8x16: u8, i16x8: i16, u16x8: u16, i32x4: i32, u32x4: u32;
+
+    BitAnd, BitAndAssign (bitand, bitand_assign):
+        i8x16: i8, u8x16: u8, i16x8: i16, u16x8: u16, i32x4: i32, u32x4: u32,
+        f32x4: f32,
+        bool8ix16: bool, bool16ix8: bool, bool32ix4: bool;
+    BitOr, BitOrAssign (bitor, bitor_assign):
+        i8x16: i8, u8x16: u8, i16x8: i16, u16x8: u16, i32x4: i32, u32x4: u32,
+        f32x4: f32,
+        bool8ix16: bool, bool16ix8: bool, bool32ix4: bool;
+    BitXor, BitXorAssign (bitxor, bitxor_assign):
+        i8x16: i8, u8x16: u8, i16x8: i16, u16x8: u16, i32x4: i32, u32x4: u32,
+        f32x4: f32,
+        bool8ix16: bool, bool16ix8: bool, bool32ix4: bool;
+}
+
+// Check every reference, scalar and assigning form of each operator
+// against the by-value form.
+#[cfg(test)]
+macro_rules! check_operator_forms {
+    ($ty: ident, $elem: ident, $length: expr, $a: expr, $b: expr; $($op: tt $op_assign: tt),*) => {{
+        let (sa, sb) = ($a as $elem, $b as $elem);
+        let (a, b) = ($ty::splat(sa), $ty::splat(sb));
+        let check = |x: $ty, want: $ty| {
+            for i in 0..$length {
+                assert_eq!(x.extract(i), want.extract(i), "{:?} != {:?}", x, want);
+            }
+        };
+        $(
+            let want = a $op b;
+            check(&a $op b, want);
+            check(a $op &b, want);
+            check(&a $op &b, want);
+            check(a $op sb, want);
+            check(sa $op b, want);
+            let mut x = a;
+            x $op_assign b;
+            check(x, want);
+            let mut x = a;
+            x $op_assign &b;
+            check(x, want);
+            let mut x = a;
+            x $op_assign sb;
+            check(x, want);
+            )*
+    }}
+}
+
+#[cfg(test)]
+mod operator_form_tests {
+    use super::*;
+
+    #[test]
+    fn f32x4_operator_forms() {
+        check_operator_forms!(f32x4, f32, 4, 7.5, -2.0;
+                              + +=, - -=, * *=, / /=, & &=, | |=, ^ ^=);
+    }
+    #[test]
+    fn i8x16_operator_forms() {
+        check_operator_forms!(i8x16, i8, 16, -7, 3;
+                              + +=, - -=, * *=, / /=, % %=, & &=, | |=, ^ ^=);
+    }
+    #[test]
+    fn u32x4_operator_forms() {
+        check_operator_forms!(u32x4, u32, 4, 1000, 7;
+                              + +=, - -=, * *=, / /=, % %=, & &=, | |=, ^ ^=);
+    }
+    #[test]
+    fn bool16ix8_operator_forms() {
+        check_operator_forms!(bool16ix8, bool, 8, true, false; & &=, | |=, ^ ^=);
+    }
 }
 
 macro_rules! shift_one {
@@ -502,6 +2838,18 @@ macro_rules! shift_one {
             fn shr(self, other: $by) -> Self {
                 unsafe {simd_shr(self, $ty::splat(other as <$ty as Simd>::Elem))}
             }
+        }
+        impl ops::ShlAssign<$by> for $ty {
+            #[inline]
+            fn shl_assign(&mut self, other: $by) {
+                *self = *self << other;
+            }
+        }
+        impl ops::ShrAssign<$by> for $ty {
+            #[inline]
+            fn shr_assign(&mut self, other: $by) {
+                *self = *self >> other;
+            }
         }
             )*
     }
@@ -519,3 +2867,295 @@ macro_rules! shift {
 shift! {
     i8x16, u8x16, i16x8, u16x8, i32x4, u32x4
 }
+
+macro_rules! vector_shifts {
+    ($($ty: ident ($count: ident, $bits: expr): $($by: ident),*;)*) => {
+        $(
+            $(
+            /// Shift each lane by the corresponding lane of the
+            /// right-hand side. Lanes shifted by the lane width or
+            /// more (including negative counts) become zero.
+            impl ops::Shl<$by> for $ty {
+                type Output = Self;
+                #[inline]
+                fn shl(self, other: $by) -> Self {
+                    let count: $count = bitcast(other);
+                    let in_range = count.lt($count::splat($bits));
+                    let count = in_range.select(count, $count::splat(0));
+                    let shifted: $ty = unsafe {simd_shl(self, bitcast(count))};
+                    in_range.select(shifted, $ty::splat(0))
+                }
+            }
+            /// Shift each lane by the corresponding lane of the
+            /// right-hand side. Lanes shifted by the lane width or
+            /// more (including negative counts) are filled with the
+            /// sign bit for signed lanes, and become zero for unsigned
+            /// ones.
+            impl ops::Shr<$by> for $ty {
+                type Output = Self;
+                #[inline]
+                fn shr(self, other: $by) -> Self {
+                    let count: $count = bitcast(other);
+                    let in_range = count.lt($count::splat($bits));
+                    let count = in_range.select(count, $count::splat($bits - 1));
+                    let shifted: $ty = unsafe {simd_shr(self, bitcast(count))};
+                    // one more bit leaves only the sign fill (or zero)
+                    let fill = unsafe {simd_shr(shifted, $ty::splat(1))};
+                    in_range.select(shifted, fill)
+                }
+            }
+            impl ops::ShlAssign<$by> for $ty {
+                #[inline]
+                fn shl_assign(&mut self, other: $by) {
+                    *self = *self << other;
+                }
+            }
+            impl ops::ShrAssign<$by> for $ty {
+                #[inline]
+                fn shr_assign(&mut self, other: $by) {
+                    *self = *self >> other;
+                }
+            }
+            )*
+
+            impl $ty {
+                /// Rotate the bits of each lane left by `n`, modulo
+                /// the lane width.
+                #[inline]
+                pub fn rotate_left(self, n: u32) -> Self {
+                    self.rotate_left_by($count::splat(n as <$count as Simd>::Elem))
+                }
+                /// Rotate the bits of each lane right by `n`, modulo
+                /// the lane width.
+                #[inline]
+                pub fn rotate_right(self, n: u32) -> Self {
+                    self.rotate_right_by($count::splat(n as <$count as Simd>::Elem))
+                }
+                /// Rotate the bits of each lane left by the
+                /// corresponding lane of `n`, modulo the lane width.
+                #[inline]
+                pub fn rotate_left_by(self, n: $count) -> Self {
+                    let mask = $count::splat($bits - 1);
+                    let x: $count = bitcast(self);
+                    unsafe {
+                        bitcast(simd_shl(x, n & mask) | simd_shr(x, ($count::splat(0) - n) & mask))
+                    }
+                }
+                /// Rotate the bits of each lane right by the
+                /// corresponding lane of `n`, modulo the lane width.
+                #[inline]
+                pub fn rotate_right_by(self, n: $count) -> Self {
+                    let mask = $count::splat($bits - 1);
+                    let x: $count = bitcast(self);
+                    unsafe {
+                        bitcast(simd_shr(x, n & mask) | simd_shl(x, ($count::splat(0) - n) & mask))
+                    }
+                }
+            }
+            )*
+    }
+}
+vector_shifts! {
+    i8x16 (u8x16, 8): i8x16, u8x16;
+    u8x16 (u8x16, 8): u8x16;
+    i16x8 (u16x8, 16): i16x8, u16x8;
+    u16x8 (u16x8, 16): u16x8;
+    i32x4 (u32x4, 32): i32x4, u32x4;
+    u32x4 (u32x4, 32): u32x4;
+}
+
+// Check the per-lane shifts and rotates against the scalar
+// `wrapping_shl`, `wrapping_shr` and `rotate_left`, with counts in
+// range, out of range and (for signed counts) negative.
+#[cfg(test)]
+macro_rules! check_shifts {
+    ($ty: ident, $elem: ident, $count: ident, $celem: ident, $length: expr) => {{
+        let bits = 8 * ::std::mem::size_of::<$elem>() as u32;
+        let xs = [1 as $elem, !0, 0x55, $elem::min_value(), $elem::max_value()];
+        // `!0` and `!0 - 1` are -1 and -2 as signed counts
+        let b = bits as $celem;
+        let cs: [$celem; 9] = [0, 1, 3, b - 1, b, b + 1, 2 * b + 3, !0, !0 - 1];
+        // the scalar shifts, with the vector rules for counts out of range
+        let shl = |x: $elem, c: u32| if c < bits { x.wrapping_shl(c) } else { 0 };
+        let shr = |x: $elem, c: u32| {
+            if c < bits { x.wrapping_shr(c) } else { x.wrapping_shr(bits - 1).wrapping_shr(1) }
+        };
+        for i in 0..cs.len() {
+            let mut by = $count::splat(0);
+            let mut by_self = $ty::splat(0);
+            for k in 0..$length {
+                by = by.replace(k, cs[(i + k as usize) % cs.len()]);
+                by_self = by_self.replace(k, cs[(i + k as usize) % cs.len()] as $elem);
+            }
+            for &x in &xs {
+                let v = $ty::splat(x);
+                let (l, r) = (v << by, v >> by);
+                let (mut l_assign, mut r_assign) = (v, v);
+                l_assign <<= by;
+                r_assign >>= by;
+                let (l_self, r_self) = (v << by_self, v >> by_self);
+                let (rl, rr) = (v.rotate_left_by(by), v.rotate_right_by(by));
+                for k in 0..$length {
+                    let c = by.extract(k) as u32;
+                    assert_eq!(l.extract(k), shl(x, c), "{:x} << {}", x, c);
+                    assert_eq!(r.extract(k), shr(x, c), "{:x} >> {}", x, c);
+                    assert_eq!(l_assign.extract(k), shl(x, c), "{:x} <<= {}", x, c);
+                    assert_eq!(r_assign.extract(k), shr(x, c), "{:x} >>= {}", x, c);
+                    assert_eq!(l_self.extract(k), shl(x, c), "{:x} << {}", x, c);
+                    assert_eq!(r_self.extract(k), shr(x, c), "{:x} >> {}", x, c);
+                    assert_eq!(rl.extract(k), x.rotate_left(c));
+                    assert_eq!(rr.extract(k), x.rotate_right(c));
+                }
+                let c = cs[i] as u32;
+                assert!(v.rotate_left(c).eq($ty::splat(x.rotate_left(c))).all());
+                assert!(v.rotate_right(c).eq($ty::splat(x.rotate_right(c))).all());
+            }
+        }
+    }}
+}
+
+#[cfg(test)]
+mod shift_tests {
+    use super::*;
+
+    #[test]
+    fn i8x16_shifts() {
+        check_shifts!(i8x16, i8, u8x16, u8, 16);
+    }
+    #[test]
+    fn u8x16_shifts() {
+        check_shifts!(u8x16, u8, u8x16, u8, 16);
+    }
+    #[test]
+    fn i16x8_shifts() {
+        check_shifts!(i16x8, i16, u16x8, u16, 8);
+    }
+    #[test]
+    fn u16x8_shifts() {
+        check_shifts!(u16x8, u16, u16x8, u16, 8);
+    }
+    #[test]
+    fn i32x4_shifts() {
+        check_shifts!(i32x4, i32, u32x4, u32, 4);
+    }
+    #[test]
+    fn u32x4_shifts() {
+        check_shifts!(u32x4, u32, u32x4, u32, 4);
+    }
+}
+
+// `u32x4` has fewer lanes than `i16x8` and `i8x16`, so they are
+// shifted by count vectors with the same number of lanes but wider
+// ones instead. Counts out of range for the narrow lanes are saturated
+// before narrowing, so they still shift everything out.
+macro_rules! wide_count_shifts {
+    ($($ty: ident ($count: ident, $bits: expr): $($by: ident ($wide: ident)),*;)*) => {
+        $(
+            $(
+            /// Shift each lane by the corresponding lane of the
+            /// right-hand side, as for a count vector with lanes as
+            /// wide as `self`'s.
+            ///
+            /// (`u32x4` has fewer lanes than `self`, so wider counts
+            /// come in a vector with as many lanes as `self` instead.)
+            impl ops::Shl<$by> for $ty {
+                type Output = Self;
+                #[inline]
+                fn shl(self, other: $by) -> Self {
+                    let count: $wide = bitcast(other);
+                    let count = count.lt($wide::splat($bits)).select(count, $wide::splat($bits));
+                    let count: $count = unsafe {simd_cast(count)};
+                    self << count
+                }
+            }
+            /// Shift each lane by the corresponding lane of the
+            /// right-hand side, as for a count vector with lanes as
+            /// wide as `self`'s.
+            ///
+            /// (`u32x4` has fewer lanes than `self`, so wider counts
+            /// come in a vector with as many lanes as `self` instead.)
+            impl ops::Shr<$by> for $ty {
+                type Output = Self;
+                #[inline]
+                fn shr(self, other: $by) -> Self {
+                    let count: $wide = bitcast(other);
+                    let count = count.lt($wide::splat($bits)).select(count, $wide::splat($bits));
+                    let count: $count = unsafe {simd_cast(count)};
+                    self >> count
+                }
+            }
+            impl ops::ShlAssign<$by> for $ty {
+                #[inline]
+                fn shl_assign(&mut self, other: $by) {
+                    *self = *self << other;
+                }
+            }
+            impl ops::ShrAssign<$by> for $ty {
+                #[inline]
+                fn shr_assign(&mut self, other: $by) {
+                    *self = *self >> other;
+                }
+            }
+            )*
+        )*
+    }
+}
+wide_count_shifts! {
+    i16x8 (u16x8, 16): i32x8 (u32x8), u32x8 (u32x8);
+    u16x8 (u16x8, 16): i32x8 (u32x8), u32x8 (u32x8);
+    i8x16 (u8x16, 8): i16x16 (u16x16), u16x16 (u16x16);
+    u8x16 (u8x16, 8): i16x16 (u16x16), u16x16 (u16x16);
+}
+
+// Check shifts by wider counts against the scalar shifts, including
+// counts that would shift by a small amount if just truncated.
+#[cfg(test)]
+macro_rules! check_wide_count_shifts {
+    ($ty: ident, $elem: ident, $by: ident, $celem: ident, $length: expr) => {{
+        let bits = 8 * ::std::mem::size_of::<$elem>() as $celem;
+        let xs = [1 as $elem, !0, 0x55, $elem::min_value(), $elem::max_value()];
+        // `1 << bits | 1` would shift by one if just truncated
+        let cs = [0, 1, bits - 1, bits, bits + 1, 1 << bits | 1, !0, !0 - bits + 2];
+        for (i, &c) in cs.iter().enumerate() {
+            let lane = (i % $length) as u32;
+            for &x in &xs {
+                let v = $ty::splat(x);
+                let by = $by::splat(0).replace(lane, c as _);
+                let (want_shl, want_shr) = if c < bits {
+                    (x.wrapping_shl(c as u32), x.wrapping_shr(c as u32))
+                } else {
+                    (0, x >> (bits - 1) >> 1)
+                };
+                assert_eq!((v << by).extract(lane), want_shl, "{:x} << {:x}", x, c);
+                assert_eq!((v >> by).extract(lane), want_shr, "{:x} >> {:x}", x, c);
+                let (mut l, mut r) = (v, v);
+                l <<= by;
+                r >>= by;
+                assert_eq!(l.extract(lane), want_shl, "{:x} <<= {:x}", x, c);
+                assert_eq!(r.extract(lane), want_shr, "{:x} >>= {:x}", x, c);
+            }
+        }
+    }}
+}
+
+#[cfg(test)]
+mod wide_count_shift_tests {
+    use super::*;
+
+    #[test]
+    fn i16x8_shift_by_u32x8() {
+        check_wide_count_shifts!(i16x8, i16, u32x8, u32, 8);
+    }
+    #[test]
+    fn u16x8_shift_by_i32x8() {
+        check_wide_count_shifts!(u16x8, u16, i32x8, u32, 8);
+    }
+    #[test]
+    fn i8x16_shift_by_i16x16() {
+        check_wide_count_shifts!(i8x16, i8, i16x16, u16, 16);
+    }
+    #[test]
+    fn u8x16_shift_by_u16x16() {
+        check_wide_count_shifts!(u8x16, u8, u16x16, u16, 16);
+    }
+}
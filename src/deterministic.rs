@@ -0,0 +1,266 @@
+//! Golden values for the operations whose results differ between
+//! platforms without the `deterministic` feature, which should be
+//! bit-identical everywhere with it:
+//!
+//! ```text
+//! cargo test --features deterministic
+//! ```
+//!
+//! The golden values are the bits of the expected results. NaN
+//! results are compared as NaNs, since their sign and payload aren't
+//! specified. They were computed by a scalar transcription of the
+//! portable code paths, run on x86_64, rather than by this crate
+//! itself, so a failure here may also be a mistake in the golden value.
+
+use f32x4;
+use sixty_four::f64x2;
+use math::Math;
+use std::{f32, f64, fmt, mem};
+
+const X32: [f32; 8] = [0.3, 1.7, -2.5, 10.0, 88.0, -100.0, 1e-3, 3.5e4];
+const Y32: [f32; 8] = [2.0, -0.5, 3.0, 0.25, 1.5, 7.0, -2.0, 0.5];
+const POS32: [f32; 8] = [0.3, 1.7, 2.5, 10.0, 1e-40, 100.0, 1e-3, 3.0e38];
+const CAST32: [f32; 8] = [1.5, -2.5, 3e9, -3e9, f32::NAN, 1e20, -0.0, 2147483520.0];
+const MIN_A32: [f32; 8] = [1.0, -0.0, 0.0, f32::NAN, 2.0, -3.0, f32::NAN, 5.0];
+const MIN_B32: [f32; 8] = [2.0, 0.0, -0.0, 1.0, f32::NAN, -4.0, f32::NAN, 5.0];
+
+const X64: [f64; 4] = [0.3, -2.5, 1e22, 700.0];
+const Y64: [f64; 4] = [2.5, 3.0, 0.5, -1.5];
+const POS64: [f64; 4] = [0.3, 1e-310, 2.0e300, 12345.678];
+const CAST64: [f64; 4] = [-2.5, 1e19, f64::NAN, -1e30];
+
+const APPROX_RSQRT: [u32; 8] = [
+    0x3fe9b1e8, 0x3f4457d1, 0x3f21e89b, 0x3ea1e89b,
+    0x60ad790a, 0x3dcccccd, 0x41fcfb71, 0x1f8852af,
+];
+const APPROX_RECIPROCAL: [u32; 8] = [
+    0x40555555, 0x3f169696, 0x3ecccccd, 0x3dcccccd,
+    0x7f800000, 0x3c23d70a, 0x4479ffff, 0x00244bfa,
+];
+// The `f32` estimates are correctly rounded here, so the steps only
+// move the last lane of `rsqrt_precise1`, by an ulp.
+const RSQRT_PRECISE1: [u32; 8] = [
+    0x3fe9b1e8, 0x3f4457d1, 0x3f21e89b, 0x3ea1e89b,
+    0x60ad790a, 0x3dcccccd, 0x41fcfb71, 0x1f8852ae,
+];
+const TO_I32: [u32; 8] = [
+    0x00000001, 0xfffffffe, 0x7fffffff, 0x80000000,
+    0x00000000, 0x7fffffff, 0x00000000, 0x7fffff80,
+];
+const TO_U32: [u32; 8] = [
+    0x00000001, 0x00000000, 0xb2d05e00, 0x00000000,
+    0x00000000, 0xffffffff, 0x00000000, 0x7fffff80,
+];
+const MIN: [u32; 8] = [
+    0x3f800000, 0x80000000, 0x80000000, 0x3f800000,
+    0x40000000, 0xc0800000, 0x7fc00000, 0x40a00000,
+];
+const MAX: [u32; 8] = [
+    0x40000000, 0x00000000, 0x00000000, 0x3f800000,
+    0x40000000, 0xc0400000, 0x7fc00000, 0x40a00000,
+];
+const MINIMUM: [u32; 8] = [
+    0x3f800000, 0x80000000, 0x80000000, 0x7fc00000,
+    0x7fc00000, 0xc0800000, 0x7fc00000, 0x40a00000,
+];
+const EXP: [u32; 8] = [
+    0x3facc82c, 0x40af2a94, 0x3da81c2e, 0x46ac14ee,
+    0x7ef882b7, 0x0000001b, 0x3f8020c9, 0x7f800000,
+];
+const LN: [u32; 8] = [
+    0xbf9a1bc8, 0x3f07d741, 0x3f6a9208, 0x40135d8e,
+    0xc2b834f2, 0x40935d8e, 0xc0dd0c55, 0x42b13196,
+];
+const SIN: [u32; 8] = [
+    0x3e974e6d, 0x3f7dddbf, 0xbf193578, 0xbf0b44f8,
+    0x3d10fdd0, 0x3f01a12e, 0x3a83126e, 0x3eee224b,
+];
+const COS: [u32; 8] = [
+    0x3f7490ef, 0xbe03efd3, 0xbf4d17bf, 0xbf56cd64,
+    0x3f7fd6ed, 0x3f5cc0ee, 0x3f7ffff8, 0xbf62a018,
+];
+const TAN: [u32; 8] = [
+    0x3e9e6153, 0xc0f64a8b, 0x3f3f3cda, 0x3f25fafa,
+    0x3d111516, 0x3f1653a7, 0x3a831272, 0xbf06800a,
+];
+const ATAN2: [u32; 8] = [
+    0x3e18769c, 0x3fedad2f, 0xbf31da5e, 0x3fc5dcd3,
+    0x3fc6e15d, 0xbfc01dd5, 0x404907a9, 0x3fc90f63,
+];
+const POW: [u32; 8] = [
+    0x3db851ec, 0x3f4457d1, 0xc17a0000, 0x3fe39ea9,
+    0x444e60d8, 0xd6b5e621, 0x497423fe, 0x433b1537,
+];
+const CBRT: [u32; 8] = [
+    0x3f2b5ff5, 0x3f98c40f, 0xbfadb905, 0x4009e242,
+    0x408e55b0, 0xc09487e5, 0x3dcccccd, 0x4202d7b8,
+];
+const TANH: [u32; 8] = [
+    0x3e9526ee, 0x3f6f76f8, 0xbf7c92c1, 0x3f800000,
+    0x3f800000, 0xbf800000, 0x3a83126c, 0x3f800000,
+];
+
+const APPROX_RSQRT_64: [u64; 4] = [
+    0x3ffd363d17fb8020, 0x601dd5574560e600,
+    0x20c2845eb589dd07, 0x3f826e965d8b1c61,
+];
+const APPROX_RECIPROCAL_64: [u64; 4] = [
+    0x400aaaaa6b74f975, 0x7ff0000000000000,
+    0x01956e174afc9c8d, 0x3f153bc9974dbe8c,
+];
+const RSQRT_PRECISE1_64: [u64; 4] = [
+    0x3ffd363d1848dcbf, 0x601dd55745cbb7fa,
+    0x20c284603e863063, 0x3f826e9799fd8194,
+];
+const RSQRT_PRECISE2_64: [u64; 4] = [
+    0x3ffd363d1848dcbf, 0x601dd55745cbb7f9,
+    0x20c284603e866142, 0x3f826e9799fda16a,
+];
+const RECIP_PRECISE1_64: [u64; 4] = [
+    0x400aaaaaaaaaaa15, 0x7ff0000000000000,
+    0x01956e1fc2f59a9c, 0x3f153bd184998562,
+];
+const RECIP_PRECISE2_64: [u64; 4] = [
+    0x400aaaaaaaaaaaab, 0x7ff0000000000000,
+    0x01956e1fc2f8f358, 0x3f153bd1849c7af8,
+];
+const TO_I64: [u64; 4] = [
+    0xfffffffffffffffe, 0x7fffffffffffffff,
+    0x0000000000000000, 0x8000000000000000,
+];
+const EXP_64: [u64; 4] = [
+    0x3ff599058c8c1a96, 0x3fb50385c094f425,
+    0x7ff0000000000000, 0x7f0d945df4f8ec8e,
+];
+const LN_64: [u64; 4] = [
+    0xbff34378fcbda721, 0xc0864e69394d9508,
+    0x40859bbfd8b83e44, 0x4022d79559791e31,
+];
+const SIN_64: [u64; 4] = [
+    0x3fd2e9cd95baba33, 0xbfe326af0dcfcab0,
+    0xbfeb453ab76bf397, 0x3fe16834defaadb0,
+];
+const TAN_64: [u64; 4] = [
+    0x3fd3cc2a44e29998, 0x3fe7e79b4e00bb14,
+    0xbffa0f79c1b6b258, 0xbfe4beaba1020052,
+];
+const POW_64: [u64; 4] = [
+    0x3fa93d32bceafc29, 0xc02f400000000000,
+    0x42374876e8000000, 0x3f0c4f137c868bbc,
+];
+
+fn bits32(x: f32) -> u32 {
+    if x != x {
+        0x7fc00000
+    } else {
+        unsafe { mem::transmute(x) }
+    }
+}
+fn bits64(x: f64) -> u64 {
+    if x != x {
+        0x7ff8000000000000
+    } else {
+        unsafe { mem::transmute(x) }
+    }
+}
+
+fn map32<F: Fn(f32x4) -> f32x4>(x: &[f32; 8], f: F) -> Vec<u32> {
+    let mut out = [0.0; 8];
+    for &i in &[0, 4] {
+        f(f32x4::load(x, i)).store(&mut out, i);
+    }
+    out.iter().map(|&x| bits32(x)).collect()
+}
+fn map32_2<F: Fn(f32x4, f32x4) -> f32x4>(x: &[f32; 8], y: &[f32; 8], f: F) -> Vec<u32> {
+    let mut out = [0.0; 8];
+    for &i in &[0, 4] {
+        f(f32x4::load(x, i), f32x4::load(y, i)).store(&mut out, i);
+    }
+    out.iter().map(|&x| bits32(x)).collect()
+}
+fn map64<F: Fn(f64x2) -> f64x2>(x: &[f64; 4], f: F) -> Vec<u64> {
+    let mut out = [0.0; 4];
+    for &i in &[0, 2] {
+        f(f64x2::load(x, i)).store(&mut out, i);
+    }
+    out.iter().map(|&x| bits64(x)).collect()
+}
+fn map64_2<F: Fn(f64x2, f64x2) -> f64x2>(x: &[f64; 4], y: &[f64; 4], f: F) -> Vec<u64> {
+    let mut out = [0.0; 4];
+    for &i in &[0, 2] {
+        f(f64x2::load(x, i), f64x2::load(y, i)).store(&mut out, i);
+    }
+    out.iter().map(|&x| bits64(x)).collect()
+}
+
+fn check<T: PartialEq + fmt::LowerHex>(name: &str, got: Vec<T>, golden: &[T]) {
+    assert_eq!(got.len(), golden.len());
+    for (i, (g, w)) in got.iter().zip(golden).enumerate() {
+        assert!(g == w, "{}[{}]: got {:#x}, expected {:#x}", name, i, g, w);
+    }
+}
+
+#[test]
+fn approx() {
+    check("approx_rsqrt", map32(&POS32, |x| x.approx_rsqrt()), &APPROX_RSQRT);
+    check("approx_reciprocal", map32(&POS32, |x| x.approx_reciprocal()), &APPROX_RECIPROCAL);
+    check("rsqrt_precise1", map32(&POS32, |x| x.rsqrt_precise1()), &RSQRT_PRECISE1);
+    check("rsqrt_precise2", map32(&POS32, |x| x.rsqrt_precise2()), &APPROX_RSQRT);
+    check("recip_precise1", map32(&POS32, |x| x.recip_precise1()), &APPROX_RECIPROCAL);
+    check("recip_precise2", map32(&POS32, |x| x.recip_precise2()), &APPROX_RECIPROCAL);
+
+    check("approx_rsqrt f64", map64(&POS64, |x| x.approx_rsqrt()), &APPROX_RSQRT_64);
+    check("approx_reciprocal f64", map64(&POS64, |x| x.approx_reciprocal()),
+          &APPROX_RECIPROCAL_64);
+    check("rsqrt_precise1 f64", map64(&POS64, |x| x.rsqrt_precise1()), &RSQRT_PRECISE1_64);
+    check("rsqrt_precise2 f64", map64(&POS64, |x| x.rsqrt_precise2()), &RSQRT_PRECISE2_64);
+    check("recip_precise1 f64", map64(&POS64, |x| x.recip_precise1()), &RECIP_PRECISE1_64);
+    check("recip_precise2 f64", map64(&POS64, |x| x.recip_precise2()), &RECIP_PRECISE2_64);
+}
+
+#[test]
+fn conversions() {
+    let mut ints = [0; 8];
+    let mut uints = [0; 8];
+    for &i in &[0, 4] {
+        f32x4::load(&CAST32, i).to_i32().store(&mut ints, i);
+        f32x4::load(&CAST32, i).to_u32().store(&mut uints, i);
+    }
+    check("to_i32", ints.iter().map(|&x| x as u32).collect(), &TO_I32);
+    check("to_u32", uints.to_vec(), &TO_U32);
+
+    let mut ints = [0; 4];
+    for &i in &[0, 2] {
+        f64x2::load(&CAST64, i).to_i64().store(&mut ints, i);
+    }
+    check("to_i64", ints.iter().map(|&x| x as u64).collect(), &TO_I64);
+}
+
+#[test]
+fn min_max() {
+    check("min", map32_2(&MIN_A32, &MIN_B32, |x, y| x.min(y)), &MIN);
+    check("max", map32_2(&MIN_A32, &MIN_B32, |x, y| x.max(y)), &MAX);
+    check("minimum", map32_2(&MIN_A32, &MIN_B32, |x, y| x.minimum(y)), &MINIMUM);
+}
+
+#[test]
+fn math32() {
+    check("exp", map32(&X32, |x| x.exp()), &EXP);
+    check("ln", map32(&POS32, |x| x.ln()), &LN);
+    check("sin", map32(&X32, |x| x.sin()), &SIN);
+    check("cos", map32(&X32, |x| x.cos()), &COS);
+    check("tan", map32(&X32, |x| x.tan()), &TAN);
+    check("atan2", map32_2(&X32, &Y32, |y, x| y.atan2(x)), &ATAN2);
+    check("pow", map32_2(&X32, &Y32, |x, y| x.pow(y)), &POW);
+    check("cbrt", map32(&X32, |x| x.cbrt()), &CBRT);
+    check("tanh", map32(&X32, |x| x.tanh()), &TANH);
+}
+
+#[test]
+fn math64() {
+    check("exp f64", map64(&X64, |x| x.exp()), &EXP_64);
+    check("ln f64", map64(&POS64, |x| x.ln()), &LN_64);
+    check("sin f64", map64(&X64, |x| x.sin()), &SIN_64);
+    check("tan f64", map64(&X64, |x| x.tan()), &TAN_64);
+    check("pow f64", map64_2(&X64, &Y64, |x, y| x.pow(y)), &POW_64);
+}
@@ -0,0 +1,136 @@
+use super::*;
+use super::simd_cast;
+use v256::u64x4;
+use std::ops;
+
+/// Division by a fixed divisor, computed with a multiplication and
+/// shifts rather than a division.
+///
+/// Creating a `Divider` does a scalar division to find the
+/// reciprocal, so this pays off when dividing many vectors by the
+/// same runtime value, e.g.
+///
+/// ```rust,ignore
+/// let buckets = Divider::<u32x4>::new(n);
+/// for x in hashes {
+///     let bucket = x % buckets;
+///     // ...
+/// }
+/// ```
+///
+/// This is the round-up method from Granlund and Montgomery,
+/// "Division by Invariant Integers using Multiplication" (1994), and
+/// gives exactly the same results as `/` and `%` for every dividend.
+#[derive(Debug, Copy, Clone)]
+pub struct Divider<T> {
+    divisor: T,
+    multiplier: T,
+    shift1: u32,
+    shift2: u32,
+}
+
+impl Divider<u32x4> {
+    /// Prepare to divide by `divisor`.
+    ///
+    /// # Panics
+    ///
+    /// `new` will panic if `divisor` is zero.
+    pub fn new(divisor: u32) -> Self {
+        assert!(divisor != 0, "attempt to divide by zero");
+        // l = ceil(log2(divisor))
+        let l = 32 - (divisor - 1).leading_zeros();
+        let m = ((1u64 << 32) * ((1u64 << l) - divisor as u64)) / divisor as u64 + 1;
+        Divider {
+            divisor: u32x4::splat(divisor),
+            multiplier: u32x4::splat(m as u32),
+            shift1: if l == 0 { 0 } else { 1 },
+            shift2: if l == 0 { 0 } else { l - 1 },
+        }
+    }
+
+    /// The divisor that `self` divides by.
+    #[inline]
+    pub fn divisor(&self) -> u32 {
+        self.divisor.extract(0)
+    }
+
+    /// Divide each lane of `x` by the divisor.
+    #[inline]
+    pub fn divide(&self, x: u32x4) -> u32x4 {
+        let t = mul_hi(self.multiplier, x);
+        (t + ((x - t) >> self.shift1)) >> self.shift2
+    }
+
+    /// Compute the remainder of dividing each lane of `x` by the
+    /// divisor.
+    #[inline]
+    pub fn remainder(&self, x: u32x4) -> u32x4 {
+        x - self.divide(x) * self.divisor
+    }
+}
+
+/// The high 32 bits of the 64-bit products of each lane.
+#[inline]
+fn mul_hi(x: u32x4, y: u32x4) -> u32x4 {
+    unsafe {
+        let x: u64x4 = simd_cast(x);
+        let y: u64x4 = simd_cast(y);
+        simd_cast((x * y) >> 32u32)
+    }
+}
+
+impl ops::Div<Divider<u32x4>> for u32x4 {
+    type Output = Self;
+    #[inline]
+    fn div(self, x: Divider<u32x4>) -> Self {
+        x.divide(self)
+    }
+}
+impl ops::Rem<Divider<u32x4>> for u32x4 {
+    type Output = Self;
+    #[inline]
+    fn rem(self, x: Divider<u32x4>) -> Self {
+        x.remainder(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dividends() -> Vec<u32> {
+        let mut xs = vec![0, 1, 2, 0x7fff_ffff, 0x8000_0000, 0x8000_0001, !0 - 1, !0];
+        let mut r = 0x1234_5678u32;
+        for _ in 0..64 {
+            r = r.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            xs.push(r);
+        }
+        xs
+    }
+
+    #[test]
+    fn matches_scalar() {
+        let mut divisors = vec![1, 2, 3, 5, 7, 10, 641, 0x8000_0001, 0xdead_beef, !0 - 1, !0];
+        divisors.extend((0..32).map(|k| 1u32 << k));
+        for &d in &divisors {
+            let div = Divider::<u32x4>::new(d);
+            assert_eq!(div.divisor(), d);
+            let mut xs = dividends();
+            xs.extend(&[d - 1, d, d.wrapping_add(1)]);
+            for x in xs.windows(4) {
+                let v = u32x4::load(x, 0);
+                let (q, r) = (v / div, v % div);
+                for i in 0..4 {
+                    assert_eq!(q.extract(i), x[i as usize] / d, "{} / {}", x[i as usize], d);
+                    assert_eq!(r.extract(i), x[i as usize] % d, "{} % {}", x[i as usize], d);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero() {
+        Divider::<u32x4>::new(0);
+    }
+}
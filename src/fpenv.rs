@@ -0,0 +1,366 @@
+//! Control of the floating point environment: flushing subnormals to
+//! zero, the rounding mode and the exception flags.
+//!
+//! These are settings of the CPU, so they apply to all floating point
+//! code on the current thread, scalar and SIMD, while they're in
+//! effect. The guards restore the previous settings when they are
+//! dropped, and can't be sent to another thread, e.g.
+//!
+//! ```rust,ignore
+//! {
+//!     let _flush = unsafe { FlushDenormals::new() };
+//!     dot(&x, &y);
+//! } // back to the default, IEEE 754 behaviour
+//! ```
+//!
+//! On x86 these are the SSE settings in MXCSR, which cover all SIMD
+//! code and scalar code on x86-64, but not the x87 unit, so this
+//! module needs SSE. On AArch64 they are the FPCR and FPSR registers.
+//!
+//! The compiler assumes the default environment, and may evaluate
+//! floating point operations at compile time or move them across the
+//! point where a guard is created or dropped. This is why creating a
+//! guard is `unsafe`: keep the code that should run under it in a
+//! separate, `#[inline(never)]` function, like `dot` in
+//! `examples/dot-product.rs`.
+
+use std::marker::PhantomData;
+
+/// The direction in which inexact results are rounded.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Rounding {
+    /// Round to the nearest value, ties to even. This is the default.
+    Nearest,
+    /// Round toward negative infinity.
+    Down,
+    /// Round toward positive infinity.
+    Up,
+    /// Round toward zero, truncating.
+    TowardZero,
+}
+
+/// The floating point exceptions that have been raised, which stay
+/// set until they're cleared with `clear_exceptions`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct Exceptions {
+    /// An operation had no meaningful result, and returned NaN.
+    pub invalid: bool,
+    /// A finite, non-zero number was divided by zero.
+    pub divide_by_zero: bool,
+    /// A result was too large to represent, and became infinite.
+    pub overflow: bool,
+    /// A result was subnormal or zero and inexact.
+    pub underflow: bool,
+    /// A result was rounded.
+    pub inexact: bool,
+}
+
+impl Exceptions {
+    /// Check whether any exception has been raised.
+    #[inline]
+    pub fn any(&self) -> bool {
+        self.invalid || self.divide_by_zero || self.overflow || self.underflow || self.inexact
+    }
+}
+
+/// Flushes subnormal numbers to zero while it is alive.
+///
+/// Subnormal results become zero, and subnormal inputs are treated
+/// as zero (FTZ and DAZ on x86, FZ on AArch64). This avoids the
+/// slow path many CPUs take for subnormals, at the cost of IEEE 754
+/// conformance.
+#[derive(Debug)]
+pub struct FlushDenormals {
+    saved: u32,
+    _thread: PhantomData<*const ()>,
+}
+
+impl FlushDenormals {
+    /// Start flushing subnormals to zero.
+    ///
+    /// # Safety
+    ///
+    /// All code on this thread sees the change until the guard is
+    /// dropped, including code that relies on subnormals for
+    /// correctness (e.g. the `math` functions, which scale subnormal
+    /// inputs, or float parsing and formatting in `std`). The caller
+    /// must make sure that no such code runs while the guard is alive,
+    /// and that guards are dropped in the reverse order they were
+    /// created in, since each one restores the settings it found.
+    #[inline]
+    pub unsafe fn new() -> FlushDenormals {
+        let saved = imp::control();
+        imp::set_control(saved | imp::FLUSH);
+        FlushDenormals {
+            saved: saved,
+            _thread: PhantomData,
+        }
+    }
+}
+
+impl Drop for FlushDenormals {
+    #[inline]
+    fn drop(&mut self) {
+        let control = imp::control() & !imp::FLUSH;
+        imp::set_control(control | (self.saved & imp::FLUSH));
+    }
+}
+
+/// Rounds inexact results in a given direction while it is alive.
+#[derive(Debug)]
+pub struct RoundingMode {
+    saved: u32,
+    _thread: PhantomData<*const ()>,
+}
+
+impl RoundingMode {
+    /// Start rounding in the direction `mode`.
+    ///
+    /// # Safety
+    ///
+    /// All code on this thread sees the change until the guard is
+    /// dropped, including code that relies on rounding to nearest for
+    /// correctness (e.g. the `math` functions, or float parsing and
+    /// formatting in `std`). The caller must make sure that no such
+    /// code runs while the guard is alive, and that guards are dropped
+    /// in the reverse order they were created in, since each one
+    /// restores the settings it found.
+    #[inline]
+    pub unsafe fn new(mode: Rounding) -> RoundingMode {
+        let saved = imp::control();
+        imp::set_control((saved & !imp::ROUNDING) | imp::rounding_bits(mode));
+        RoundingMode {
+            saved: saved,
+            _thread: PhantomData,
+        }
+    }
+
+    /// The current rounding direction.
+    #[inline]
+    pub fn current() -> Rounding {
+        imp::rounding(imp::control() & imp::ROUNDING)
+    }
+}
+
+impl Drop for RoundingMode {
+    #[inline]
+    fn drop(&mut self) {
+        let control = imp::control() & !imp::ROUNDING;
+        imp::set_control(control | (self.saved & imp::ROUNDING));
+    }
+}
+
+/// Read the exception flags.
+#[inline]
+pub fn exceptions() -> Exceptions {
+    let status = imp::status();
+    Exceptions {
+        invalid: status & imp::INVALID != 0,
+        divide_by_zero: status & imp::DIVIDE_BY_ZERO != 0,
+        overflow: status & imp::OVERFLOW != 0,
+        underflow: status & imp::UNDERFLOW != 0,
+        inexact: status & imp::INEXACT != 0,
+    }
+}
+
+/// Clear the exception flags.
+#[inline]
+pub fn clear_exceptions() {
+    imp::set_status(imp::status() & !imp::EXCEPTIONS);
+}
+
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"),
+          target_feature = "sse"))]
+mod imp {
+    use super::Rounding;
+
+    extern {
+        #[link_name = "llvm.x86.sse.stmxcsr"]
+        fn x86_sse_stmxcsr(p: *mut i8);
+        #[link_name = "llvm.x86.sse.ldmxcsr"]
+        fn x86_sse_ldmxcsr(p: *const i8);
+    }
+
+    pub const FLUSH: u32 = 1 << 15 | 1 << 6; // FTZ and DAZ
+    pub const ROUNDING: u32 = 3 << 13;
+    pub const INVALID: u32 = 1 << 0;
+    pub const DIVIDE_BY_ZERO: u32 = 1 << 2;
+    pub const OVERFLOW: u32 = 1 << 3;
+    pub const UNDERFLOW: u32 = 1 << 4;
+    pub const INEXACT: u32 = 1 << 5;
+    // including the denormal flag, bit 1
+    pub const EXCEPTIONS: u32 = 0x3f;
+
+    #[inline]
+    pub fn rounding_bits(mode: Rounding) -> u32 {
+        match mode {
+            Rounding::Nearest => 0 << 13,
+            Rounding::Down => 1 << 13,
+            Rounding::Up => 2 << 13,
+            Rounding::TowardZero => 3 << 13,
+        }
+    }
+    #[inline]
+    pub fn rounding(bits: u32) -> Rounding {
+        match bits >> 13 {
+            0 => Rounding::Nearest,
+            1 => Rounding::Down,
+            2 => Rounding::Up,
+            _ => Rounding::TowardZero,
+        }
+    }
+
+    #[inline]
+    pub fn control() -> u32 {
+        let mut csr = 0u32;
+        unsafe { x86_sse_stmxcsr(&mut csr as *mut u32 as *mut i8) }
+        csr
+    }
+    #[inline]
+    pub fn set_control(csr: u32) {
+        unsafe { x86_sse_ldmxcsr(&csr as *const u32 as *const i8) }
+    }
+    // the flags are in MXCSR too
+    #[inline]
+    pub fn status() -> u32 {
+        control()
+    }
+    #[inline]
+    pub fn set_status(csr: u32) {
+        set_control(csr)
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod imp {
+    use super::Rounding;
+
+    pub const FLUSH: u32 = 1 << 24; // FZ
+    pub const ROUNDING: u32 = 3 << 22;
+    pub const INVALID: u32 = 1 << 0;
+    pub const DIVIDE_BY_ZERO: u32 = 1 << 1;
+    pub const OVERFLOW: u32 = 1 << 2;
+    pub const UNDERFLOW: u32 = 1 << 3;
+    pub const INEXACT: u32 = 1 << 4;
+    // including the input denormal flag, bit 7
+    pub const EXCEPTIONS: u32 = 0x9f;
+
+    #[inline]
+    pub fn rounding_bits(mode: Rounding) -> u32 {
+        match mode {
+            Rounding::Nearest => 0 << 22,
+            Rounding::Up => 1 << 22,
+            Rounding::Down => 2 << 22,
+            Rounding::TowardZero => 3 << 22,
+        }
+    }
+    #[inline]
+    pub fn rounding(bits: u32) -> Rounding {
+        match bits >> 22 {
+            0 => Rounding::Nearest,
+            1 => Rounding::Up,
+            2 => Rounding::Down,
+            _ => Rounding::TowardZero,
+        }
+    }
+
+    #[inline]
+    pub fn control() -> u32 {
+        let fpcr: u64;
+        unsafe { asm!("mrs $0, fpcr" : "=r"(fpcr) : : : "volatile") }
+        fpcr as u32
+    }
+    #[inline]
+    pub fn set_control(fpcr: u32) {
+        unsafe { asm!("msr fpcr, $0" : : "r"(fpcr as u64) : : "volatile") }
+    }
+    #[inline]
+    pub fn status() -> u32 {
+        let fpsr: u64;
+        unsafe { asm!("mrs $0, fpsr" : "=r"(fpsr) : : : "volatile") }
+        fpsr as u32
+    }
+    #[inline]
+    pub fn set_status(fpsr: u32) {
+        unsafe { asm!("msr fpsr, $0" : : "r"(fpsr as u64) : : "volatile") }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use f32x4;
+    use std::{f32, f64, ptr};
+
+    // the compiler can't fold these, so the arithmetic happens under
+    // the guards
+    fn opaque<T: Copy>(x: T) -> T {
+        unsafe { ptr::read_volatile(&x) }
+    }
+
+    #[test]
+    fn rounding_mode_save_restore() {
+        let third = 1.0f32 / 3.0;
+        assert_eq!(RoundingMode::current(), Rounding::Nearest);
+        {
+            let _down = unsafe { RoundingMode::new(Rounding::Down) };
+            assert_eq!(RoundingMode::current(), Rounding::Down);
+            assert!(opaque(1.0f32) / opaque(3.0) < third);
+            let x = f32x4::splat(opaque(1.0)) / f32x4::splat(opaque(3.0));
+            assert!(x.lt(f32x4::splat(third)).all());
+            {
+                let _zero = unsafe { RoundingMode::new(Rounding::TowardZero) };
+                assert_eq!(RoundingMode::current(), Rounding::TowardZero);
+                assert!(opaque(-1.0f32) / opaque(3.0) > -third);
+            }
+            assert_eq!(RoundingMode::current(), Rounding::Down);
+        }
+        assert_eq!(RoundingMode::current(), Rounding::Nearest);
+        assert_eq!(opaque(1.0f32) / opaque(3.0), third);
+    }
+
+    #[test]
+    fn flush_denormals_save_restore() {
+        let tiny = f32::MIN_POSITIVE;
+        let before = imp::control();
+        assert!(opaque(tiny) / opaque(2.0) > 0.0);
+        {
+            let _flush = unsafe { FlushDenormals::new() };
+            assert_eq!(opaque(tiny) / opaque(2.0), 0.0);
+            let x = f32x4::splat(opaque(tiny)) / f32x4::splat(opaque(2.0));
+            assert!(x.eq(f32x4::splat(0.0)).all());
+            {
+                // the inner guard only restores the rounding bits
+                let _up = unsafe { RoundingMode::new(Rounding::Up) };
+                assert_eq!(opaque(tiny) / opaque(2.0), 0.0);
+            }
+            assert_eq!(RoundingMode::current(), Rounding::Nearest);
+            assert_eq!(opaque(tiny) / opaque(2.0), 0.0);
+        }
+        assert!(opaque(tiny) / opaque(2.0) > 0.0);
+        assert_eq!(imp::control() & (imp::FLUSH | imp::ROUNDING),
+                   before & (imp::FLUSH | imp::ROUNDING));
+    }
+
+    #[test]
+    fn exception_flags() {
+        clear_exceptions();
+        assert!(!exceptions().any());
+
+        opaque(opaque(1.0f64) / opaque(0.0));
+        assert_eq!(exceptions(),
+                   Exceptions { divide_by_zero: true, ..Exceptions::default() });
+
+        opaque(opaque(0.0f64) / opaque(0.0));
+        assert!(exceptions().invalid);
+        opaque(opaque(f64::MAX) * opaque(2.0));
+        assert!(exceptions().overflow);
+        opaque(opaque(f64::MIN_POSITIVE) * opaque(f64::EPSILON) / opaque(3.0));
+        assert!(exceptions().underflow);
+        opaque(opaque(1.0f64) / opaque(3.0));
+        assert!(exceptions().inexact);
+
+        clear_exceptions();
+        assert_eq!(exceptions(), Exceptions::default());
+    }
+}
@@ -1,6 +1,25 @@
 //! `simd` offers a basic interface to the SIMD functionality of CPUs.
+//!
+//! # Deterministic results
+//!
+//! Most operations on floats are IEEE 754 operations, and give the same
+//! results on every platform. A few are not: `approx_rsqrt` and
+//! `approx_reciprocal` use each platform's estimate instructions,
+//! `to_i32` and friends give whatever the conversion instruction gives
+//! for NaN and out of range lanes, and the `math` functions use the
+//! platform's scalar functions for huge trigonometric arguments. With
+//! the `deterministic` feature, these are replaced by portable
+//! versions, so that every operation outside the platform specific
+//! modules gives bit-identical results everywhere, at some cost in
+//! speed; `cargo test --features deterministic` checks this against
+//! golden values.
+//!
+//! This assumes the default floating point environment, in which
+//! subnormals are not flushed to zero. The sign and payload of NaN
+//! results still differ between platforms, as for scalar floats.
 
-#![feature(cfg_target_feature, repr_simd, platform_intrinsics, const_fn)]
+#![feature(cfg_target_feature, repr_simd, platform_intrinsics, const_fn,
+           link_llvm_intrinsics, asm)]
 #![allow(non_camel_case_types)]
 
 #[cfg(feature = "with-serde")]
@@ -9,6 +28,8 @@ extern crate serde;
 #[macro_use]
 extern crate serde_derive;
 
+use std::fmt;
+
 /// Boolean type for 8-bit integers.
 #[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -17,14 +38,13 @@ pub struct bool8i(i8);
 #[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct bool16i(i16);
-/// Boolean type for 32-bit integers.
+/// Boolean type for 32-bit integers and floats.
 #[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct bool32i(i32);
 /// Boolean type for 32-bit floats.
-#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
-pub struct bool32f(i32);
+#[deprecated(note = "use `bool32i`, which is shared by integers and floats")]
+pub type bool32f = bool32i;
 
 macro_rules! bool {
     ($($name: ident, $inner: ty;)*) => {
@@ -48,7 +68,6 @@ bool! {
     bool8i, i8;
     bool16i, i16;
     bool32i, i32;
-    bool32f, i32;
 }
 
 /// Types that are SIMD vectors.
@@ -74,16 +93,15 @@ pub struct i32x4(i32, i32, i32, i32);
 #[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Copy)]
 pub struct f32x4(f32, f32, f32, f32);
-/// A SIMD boolean vector for length-4 vectors of 32-bit integers.
+/// A SIMD boolean vector for length-4 vectors of 32-bit integers
+/// or floats.
 #[repr(simd)]
 #[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Copy)]
 pub struct bool32ix4(i32, i32, i32, i32);
 /// A SIMD boolean vector for length-4 vectors of 32-bit floats.
-#[repr(simd)]
-#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
-#[derive(Debug, Copy)]
-pub struct bool32fx4(i32, i32, i32, i32);
+#[deprecated(note = "use `bool32ix4`, which works with `f32x4` too")]
+pub type bool32fx4 = bool32ix4;
 
 #[allow(dead_code)]
 #[repr(simd)]
@@ -105,11 +123,6 @@ struct f32x2(f32, f32);
 #[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Copy)]
 struct bool32ix2(i32, i32);
-#[allow(dead_code)]
-#[repr(simd)]
-#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
-#[derive(Debug, Copy)]
-struct bool32fx2(i32, i32);
 
 /// A SIMD vector of 8 `u16`s.
 #[repr(simd)]
@@ -162,11 +175,9 @@ macro_rules! simd {
 simd! {
     bool8ix16: i8x16 = i8, u8x16 = u8, bool8ix16 = bool8i;
     bool16ix8: i16x8 = i16, u16x8 = u16, bool16ix8 = bool16i;
-    bool32ix4: i32x4 = i32, u32x4 = u32, bool32ix4 = bool32i;
-    bool32fx4: f32x4 = f32, bool32fx4 = bool32f;
+    bool32ix4: i32x4 = i32, u32x4 = u32, f32x4 = f32, bool32ix4 = bool32i;
 
-    bool32ix2: i32x2 = i32, u32x2 = u32, bool32ix2 = bool32i;
-    bool32fx2: f32x2 = f32, bool32fx2 = bool32f;
+    bool32ix2: i32x2 = i32, u32x2 = u32, f32x2 = f32, bool32ix2 = bool32i;
 }
 
 #[allow(dead_code)]
@@ -190,6 +201,7 @@ extern "platform-intrinsic" {
     fn simd_shuffle4<T: Simd, U: Simd<Elem = T::Elem>>(x: T, y: T, idx: [u32; 4]) -> U;
     fn simd_shuffle8<T: Simd, U: Simd<Elem = T::Elem>>(x: T, y: T, idx: [u32; 8]) -> U;
     fn simd_shuffle16<T: Simd, U: Simd<Elem = T::Elem>>(x: T, y: T, idx: [u32; 16]) -> U;
+    fn simd_shuffle32<T: Simd, U: Simd<Elem = T::Elem>>(x: T, y: T, idx: [u32; 32]) -> U;
 
     fn simd_insert<T: Simd<Elem = U>, U>(x: T, idx: u32, val: U) -> T;
     fn simd_extract<T: Simd<Elem = U>, U>(x: T, idx: u32) -> U;
@@ -210,10 +222,138 @@ extern "platform-intrinsic" {
 #[derive(Debug, Copy, Clone)]
 struct Unalign<T>(T);
 
+/// An iterator over the indices of the true lanes of a boolean
+/// vector, in increasing order.
+///
+/// This is created by the `true_lanes` method of the boolean vectors.
+#[derive(Debug, Clone)]
+pub struct TrueLanes {
+    bits: u64,
+}
+
+impl Iterator for TrueLanes {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        if self.bits == 0 {
+            None
+        } else {
+            let idx = self.bits.trailing_zeros();
+            self.bits &= self.bits - 1;
+            Some(idx as usize)
+        }
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.bits.count_ones() as usize;
+        (n, Some(n))
+    }
+}
+impl DoubleEndedIterator for TrueLanes {
+    #[inline]
+    fn next_back(&mut self) -> Option<usize> {
+        if self.bits == 0 {
+            None
+        } else {
+            let idx = 63 - self.bits.leading_zeros();
+            self.bits &= !(1 << idx);
+            Some(idx as usize)
+        }
+    }
+}
+impl ExactSizeIterator for TrueLanes {}
+
+/// An error from parsing a vector from a string.
+///
+/// Vectors parse from the same form that `Display` prints,
+/// e.g. `[1, 2, 3, 4]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseVectorError {
+    kind: ParseVectorErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ParseVectorErrorKind {
+    Brackets,
+    LaneCount,
+    Lane,
+}
+
+impl std::error::Error for ParseVectorError {
+    fn description(&self) -> &str {
+        match self.kind {
+            ParseVectorErrorKind::Brackets => "vector not enclosed in brackets",
+            ParseVectorErrorKind::LaneCount => "wrong number of lanes in vector",
+            ParseVectorErrorKind::Lane => "invalid lane in vector",
+        }
+    }
+}
+impl fmt::Display for ParseVectorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(std::error::Error::description(self))
+    }
+}
+
+// Format `lanes` as `[a, b, c, d]`, applying the flags of `f` (such
+// as `#` or a precision) to each lane.
+#[allow(dead_code)]
+fn fmt_lanes<T>(lanes: &[T], f: &mut fmt::Formatter,
+                fmt_lane: fn(&T, &mut fmt::Formatter) -> fmt::Result) -> fmt::Result {
+    f.write_str("[")?;
+    for (i, x) in lanes.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        fmt_lane(x, f)?;
+    }
+    f.write_str("]")
+}
+
+// Parse the form written by `fmt_lanes`, filling in every lane.
+#[allow(dead_code)]
+fn parse_lanes<T: std::str::FromStr>(s: &str, lanes: &mut [T]) -> Result<(), ParseVectorError> {
+    let s = s.trim();
+    if s.len() < 2 || !s.starts_with('[') || !s.ends_with(']') {
+        return Err(ParseVectorError { kind: ParseVectorErrorKind::Brackets });
+    }
+    let mut parts = s[1..s.len() - 1].split(',');
+    for lane in lanes.iter_mut() {
+        let part = match parts.next() {
+            Some(part) => part,
+            None => return Err(ParseVectorError { kind: ParseVectorErrorKind::LaneCount }),
+        };
+        *lane = match part.trim().parse() {
+            Ok(x) => x,
+            Err(_) => return Err(ParseVectorError { kind: ParseVectorErrorKind::Lane }),
+        };
+    }
+    if parts.next().is_some() {
+        return Err(ParseVectorError { kind: ParseVectorErrorKind::LaneCount });
+    }
+    Ok(())
+}
+
 #[macro_use]
 mod common;
 mod sixty_four;
 mod v256;
+mod divider;
+mod sum;
+mod bits;
+#[cfg(all(test, feature = "deterministic"))]
+mod deterministic;
+
+pub use divider::Divider;
+pub use sum::SimdSum;
+pub use bits::{FromBits, IntoBits};
+
+pub mod math;
+#[cfg(any(all(any(target_arch = "x86",
+                  target_arch = "x86_64"),
+              target_feature = "sse"),
+          target_arch = "aarch64"))]
+pub mod fpenv;
 
 #[cfg(any(feature = "doc",
           target_arch = "x86",
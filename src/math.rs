@@ -0,0 +1,1298 @@
+//! Elementary functions on floating point vectors.
+//!
+//! The `Math` trait provides exponentials, logarithms, trigonometric
+//! and hyperbolic functions for `f32x4` and `f64x2` (and `f32x8` and
+//! `f64x4`, when they are available), computed on all lanes at once
+//! rather than by calling the scalar functions one lane at a time.
+//!
+//! The functions follow the scalar functions in `std` for special
+//! values: NaNs propagate, infinities and signed zeros give the
+//! limits that IEEE 754 specifies, and subnormal inputs and outputs
+//! are handled with full accuracy. The maximum error of each
+//! function, in units in the last place (ULP), is the following,
+//! measured against the scalar `f64` functions in `std` (rounded to
+//! `f32` for `f32` lanes) over a sample of inputs:
+//!
+//! | function | `f32` lanes | `f64` lanes |
+//! |----------|-------------|-------------|
+//! | `exp`    | 1           | 2           |
+//! | `exp2`   | 2           | 1           |
+//! | `ln`     | 1           | 1           |
+//! | `log2`   | 1           | 1           |
+//! | `log10`  | 1           | 2           |
+//! | `sin`    | 1           | 1           |
+//! | `cos`    | 1           | 1           |
+//! | `sincos` | 1           | 1           |
+//! | `tan`    | 1           | 2           |
+//! | `atan`   | 3           | 1           |
+//! | `atan2`  | 4           | 2           |
+//! | `pow`    | 1           | 1           |
+//! | `cbrt`   | 1           | 1           |
+//! | `tanh`   | 2           | 2           |
+//!
+//! The trigonometric functions reduce their argument modulo π/2 with
+//! a three-part approximation to π/2, as fdlibm does, which is exact
+//! for `|x| < 2^20 * π/2`. Vectors with a lane outside that range, or
+//! an infinite or NaN lane, are computed with the scalar functions
+//! instead, so the bounds above hold for every input. With the
+//! `deterministic` feature, the scalar functions (which differ between
+//! platforms) aren't used, and those lanes are reduced one at a time
+//! with the Payne-Hanek method. On `f32` lanes, the trigonometric
+//! functions and `pow` are computed on `f64` lanes and rounded once.
+//! On `f64` lanes, `pow` carries the logarithm and `y * log2(x)` in
+//! double-double, since the error of a plain `f64` logarithm is
+//! magnified when the product is large.
+//!
+//! Apart from the scalar trigonometric fallback, everything is built
+//! from IEEE 754 arithmetic only, so the results are the same on every
+//! platform.
+
+
+use super::*;
+use super::{simd_cast, simd_shuffle8, bitcast};
+use sixty_four::*;
+use v256::*;
+use std::mem;
+
+/// Elementary functions, computed lane-wise.
+pub trait Math: Sized {
+    /// Compute `e^self` for each lane.
+    fn exp(self) -> Self;
+    /// Compute `2^self` for each lane.
+    fn exp2(self) -> Self;
+    /// Compute the natural logarithm of each lane.
+    fn ln(self) -> Self;
+    /// Compute the base 2 logarithm of each lane.
+    fn log2(self) -> Self;
+    /// Compute the base 10 logarithm of each lane.
+    fn log10(self) -> Self;
+    /// Compute the sine of each lane (in radians).
+    fn sin(self) -> Self;
+    /// Compute the cosine of each lane (in radians).
+    fn cos(self) -> Self;
+    /// Compute the sine and the cosine of each lane (in radians).
+    ///
+    /// This is equivalent to `(self.sin(), self.cos())`, but shares
+    /// the argument reduction between the two.
+    fn sincos(self) -> (Self, Self);
+    /// Compute the tangent of each lane (in radians).
+    fn tan(self) -> Self;
+    /// Compute the arctangent of each lane, in the range `[-π/2,
+    /// π/2]`.
+    fn atan(self) -> Self;
+    /// Compute the four quadrant arctangent of `self` (`y`) and
+    /// `other` (`x`) in each lane, in the range `[-π, π]`.
+    fn atan2(self, other: Self) -> Self;
+    /// Raise each lane of `self` to the power of the corresponding
+    /// lane of `n`.
+    fn pow(self, n: Self) -> Self;
+    /// Compute the cube root of each lane.
+    fn cbrt(self) -> Self;
+    /// Compute the hyperbolic tangent of each lane.
+    fn tanh(self) -> Self;
+}
+
+// Building blocks shared by both float widths. These are only valid
+// for the ranges documented on each, which the callers ensure.
+macro_rules! helpers {
+    ($f: ident, $i: ident, $u: ident, $felem: ident, $length: expr,
+     $to_int: ident, $to_float: ident, $mant: expr, $bias: expr) => {
+        #[inline]
+        fn splat(x: $felem) -> $f {
+            $f::splat(x)
+        }
+        #[inline]
+        fn bits(x: $f) -> $u {
+            bitcast(x)
+        }
+        #[inline]
+        fn ibits(x: $f) -> $i {
+            bitcast(x)
+        }
+        #[inline]
+        fn from_bits(x: $u) -> $f {
+            bitcast(x)
+        }
+        #[inline]
+        fn from_ibits(x: $i) -> $f {
+            bitcast(x)
+        }
+        #[inline]
+        fn abs(x: $f) -> $f {
+            from_bits(bits(x) & $u::splat(!0 >> 1))
+        }
+        /// The sign bit of each lane.
+        #[inline]
+        fn sign(x: $f) -> $u {
+            bits(x) & $u::splat(!(!0 >> 1))
+        }
+        #[inline]
+        fn xor_sign(x: $f, sign: $u) -> $f {
+            from_bits(bits(x) ^ sign)
+        }
+        /// Round to the nearest integer, ties to even.
+        #[inline]
+        fn round(x: $f) -> $f {
+            let ax = abs(x);
+            let limit = splat((1u64 << $mant) as $felem);
+            let rounded = from_bits(bits((ax + limit) - limit) | sign(x));
+            ax.lt(limit).select(rounded, x)
+        }
+        /// `2^n`, for `n` in the normal exponent range.
+        #[inline]
+        fn pow2i(n: $i) -> $f {
+            from_ibits((n + $i::splat($bias)) << $mant)
+        }
+        /// `x * 2^n`, for `n` up to twice the normal exponent range.
+        #[inline]
+        fn ldexp(x: $f, n: $i) -> $f {
+            let n1 = n >> 1;
+            let n2 = n - n1;
+            x * pow2i(n1) * pow2i(n2)
+        }
+
+        pub fn atan(x: $f) -> $f {
+            xor_sign(atan_abs(abs(x)), sign(x))
+        }
+        pub fn atan2(y: $f, x: $f) -> $f {
+            let ax = abs(x);
+            let ay = abs(y);
+            // atan2(±inf, ±inf) is an odd multiple of π/4.
+            let both_inf = ax.eq(splat(INF)) & ay.eq(splat(INF));
+            let ax_ = both_inf.select(splat(1.0), ax);
+            let ay_ = both_inf.select(splat(1.0), ay);
+            let t = ay.eq(splat(0.0)).select(splat(0.0), atan_abs(ay_ / ax_));
+            let negative = sign(x).ne($u::splat(0));
+            let t = negative.select(splat(PI) - (t - splat(PI_LO)), t);
+            let r = xor_sign(t, sign(y));
+            (x.ne(x) | y.ne(y)).select(x + y, r)
+        }
+
+        pub fn ln(x: $f) -> $f {
+            let (f, dk) = log_reduce(x);
+            let (s, hfsq, r) = log_kernel(f);
+            log_special(x, s * (hfsq + r) + dk * splat(LN2_LO) - hfsq + f + dk * splat(LN2_HI))
+        }
+        /// Fix up the results of logarithms for zero, negative,
+        /// infinite and NaN inputs.
+        #[inline]
+        fn log_special(x: $f, r: $f) -> $f {
+            let r = x.eq(splat(INF)).select(x, r);
+            let r = x.eq(splat(0.0)).select(splat(-INF), r);
+            let r = x.lt(splat(0.0)).select(splat(NAN), r);
+            x.ne(x).select(x, r)
+        }
+        /// Split `x`, which is positive and finite, into `1 + f` in
+        /// `[√2/2, √2)` and the exponent `k`, returned as a float.
+        #[inline]
+        fn log_reduce(x: $f) -> ($f, $f) {
+            let sub = x.lt(splat(MIN_POSITIVE));
+            let x = sub.select(x * splat(LOG_SUB_SCALE), x);
+            let k = sub.select($i::splat(-LOG_SUB_SHIFT), $i::splat(0));
+            let ix = ibits(x) + $i::splat(ONE_BITS - SQRT_HALF_BITS);
+            let k = k + (ix >> ($mant as u32)) - $i::splat($bias);
+            let ix = (ix & $i::splat((1 << $mant) - 1)) + $i::splat(SQRT_HALF_BITS);
+            (from_ibits(ix) - splat(1.0), k.$to_float())
+        }
+        /// Split `f - f * f / 2` into `hi + lo`, where `hi` has few
+        /// enough bits that `hi * C_HI` is exact.
+        #[inline]
+        fn log_hi_lo(f: $f) -> ($f, $f) {
+            let (s, hfsq, r) = log_kernel(f);
+            let hi = f - hfsq;
+            let hi = from_bits(bits(hi) & $u::splat(LOG_HI_MASK));
+            let lo = f - hi - hfsq + s * (hfsq + r);
+            (hi, lo)
+        }
+
+        pub fn cbrt(x: $f) -> $f {
+            let ax = abs(x);
+            // scale subnormal lanes up, and huge lanes down so that
+            // `t^3` can't overflow
+            let sub = ax.lt(splat(MIN_POSITIVE));
+            let huge = ax.ge(splat(CBRT_HUGE));
+            let a = sub.select(ax * splat(CBRT_SUB_SCALE),
+                               huge.select(ax * splat(1.0 / CBRT_HUGE), ax));
+            // divide the exponent by three to get a first guess
+            let guess = (ibits(a).$to_float() * splat(1.0 / 3.0)).$to_int();
+            let mut t = from_ibits(guess + $i::splat(CBRT_BIAS));
+            for _ in 0..CBRT_ITERATIONS {
+                // Halley's method
+                let t3 = t * t * t;
+                t = t + t * ((a - t3) / (t3 + t3 + a));
+            }
+            let t = sub.select(t * splat(CBRT_SUB_UNSCALE),
+                               huge.select(t * splat(CBRT_HUGE_UNSCALE), t));
+            let r = xor_sign(t, sign(x));
+            (ax.eq(splat(0.0)) | !ax.lt(splat(INF))).select(x, r)
+        }
+
+        pub fn tanh(x: $f) -> $f {
+            let ax = abs(x);
+            let large = splat(1.0) - splat(2.0) / (exp(ax + ax) + splat(1.0));
+            let r = ax.ge(splat(0.625)).select(large, tanh_poly(ax));
+            xor_sign(r, sign(x))
+        }
+    }
+}
+
+macro_rules! f32_math {
+    ($($module: ident: $f: ident, $i: ident, $u: ident, $length: expr,
+       $sincos: ident, $tan: ident, $pow_abs: ident;)*) => {
+        $(
+            mod $module {
+                use super::*;
+                use std::f32::{INFINITY as INF, NAN, MIN_POSITIVE};
+
+                helpers!($f, $i, $u, f32, $length, to_i32, to_f32, 23, 127);
+
+                const PI: f32 = 3.14159265358979323846;
+                const PI_LO: f32 = -8.742278e-8;
+
+                const LOG2_E: f32 = 1.44269504088896341;
+                const LN2_HI: f32 = 0.6931381225585938;
+                const LN2_LO: f32 = 9.05800061445916e-06;
+
+                pub fn exp(x: $f) -> $f {
+                    let big = x.gt(splat(88.72283905206835));
+                    let small = x.lt(splat(-103.97208));
+                    let nan = x.ne(x);
+                    let x_ = (big | small | nan).select(splat(0.0), x);
+                    let n = round(x_ * splat(LOG2_E));
+                    let r = x_ - n * splat(0.693359375) - n * splat(-2.12194440e-4);
+                    let z = r * r;
+                    let p = (((((splat(1.9875691500E-4) * r
+                                 + splat(1.3981999507E-3)) * r
+                                + splat(8.3334519073E-3)) * r
+                               + splat(4.1665795894E-2)) * r
+                              + splat(1.6666665459E-1)) * r
+                             + splat(5.0000001201E-1)) * z + r + splat(1.0);
+                    let y = ldexp(p, n.to_i32());
+                    let y = big.select(splat(INF), y);
+                    let y = small.select(splat(0.0), y);
+                    nan.select(x, y)
+                }
+
+                pub fn exp2(x: $f) -> $f {
+                    let big = x.ge(splat(128.0));
+                    let small = x.lt(splat(-150.0));
+                    let nan = x.ne(x);
+                    let x_ = (big | small | nan).select(splat(0.0), x);
+                    let n = round(x_);
+                    let r = x_ - n;
+                    let p = (((((splat(1.535336188319500E-4) * r
+                                 + splat(1.339887440266574E-3)) * r
+                                + splat(9.618437357674640E-3)) * r
+                               + splat(5.550332471162809E-2)) * r
+                              + splat(2.402264791363012E-1)) * r
+                             + splat(6.931472028550421E-1)) * r + splat(1.0);
+                    let y = ldexp(p, n.to_i32());
+                    let y = big.select(splat(INF), y);
+                    let y = small.select(splat(0.0), y);
+                    nan.select(x, y)
+                }
+
+                const LOG_SUB_SCALE: f32 = 33554432.0; // 2^25
+                const LOG_SUB_SHIFT: i32 = 25;
+                const ONE_BITS: i32 = 0x3f800000;
+                const SQRT_HALF_BITS: i32 = 0x3f3504f3;
+                const LOG_HI_MASK: u32 = 0xfffff000;
+
+                #[inline]
+                fn log_kernel(f: $f) -> ($f, $f, $f) {
+                    let s = f / (splat(2.0) + f);
+                    let z = s * s;
+                    let w = z * z;
+                    let t1 = w * (splat(0.40000972151756287) + w * splat(0.24279078841209412));
+                    let t2 = z * (splat(0.6666666269302368) + w * splat(0.2849878668785095));
+                    (s, splat(0.5) * f * f, t2 + t1)
+                }
+
+                pub fn log2(x: $f) -> $f {
+                    let (f, dk) = log_reduce(x);
+                    let (hi, lo) = log_hi_lo(f);
+                    log_special(x, (lo + hi) * splat(-0.00017605285393074155)
+                                + lo * splat(1.44287109375) + hi * splat(1.44287109375) + dk)
+                }
+
+                pub fn log10(x: $f) -> $f {
+                    let (f, dk) = log_reduce(x);
+                    let (hi, lo) = log_hi_lo(f);
+                    log_special(x, dk * splat(7.903415166765626e-07)
+                                + (lo + hi) * splat(-3.168997136526741e-05)
+                                + lo * splat(0.434326171875) + hi * splat(0.434326171875)
+                                + dk * splat(0.3010292053222656))
+                }
+
+                pub fn sincos(x: $f) -> ($f, $f) {
+                    super::$sincos(x)
+                }
+                pub fn sin(x: $f) -> $f {
+                    sincos(x).0
+                }
+                pub fn cos(x: $f) -> $f {
+                    sincos(x).1
+                }
+                pub fn tan(x: $f) -> $f {
+                    super::$tan(x)
+                }
+
+                /// `atan` of a non-negative (or NaN) `ax`.
+                #[inline]
+                fn atan_abs(ax: $f) -> $f {
+                    let big = ax.gt(splat(2.414213562373095));
+                    let mid = ax.gt(splat(0.4142135623730950)) & !big;
+                    let x = big.select(splat(-1.0) / ax,
+                                       mid.select((ax - splat(1.0)) / (ax + splat(1.0)), ax));
+                    let y = big.select(splat(PI / 2.0),
+                                       mid.select(splat(PI / 4.0), splat(0.0)));
+                    let z = x * x;
+                    y + ((((splat(8.05374449538e-2) * z + splat(-1.38776856032E-1)) * z
+                           + splat(1.99777106478E-1)) * z + splat(-3.33329491539E-1)) * z * x + x)
+                }
+
+                pub fn pow(x: $f, y: $f) -> $f {
+                    let ax = abs(x);
+                    let ay = abs(y);
+                    let r = super::$pow_abs(ax, y);
+                    let int = round(y).eq(y);
+                    let y_ = ay.lt(splat(16777216.0)).select(y, splat(0.0));
+                    let odd = int & (y_.to_i32() & $i::splat(1)).eq($i::splat(1));
+                    let r = xor_sign(r, sign(x) & odd.select($u::splat(!0), $u::splat(0)));
+                    let r = (x.lt(splat(0.0)) & x.gt(splat(-INF)) & !int).select(splat(NAN), r);
+                    let r = (ax.eq(splat(1.0)) & ay.eq(splat(INF))).select(splat(1.0), r);
+                    (y.eq(splat(0.0)) | x.eq(splat(1.0))).select(splat(1.0), r)
+                }
+
+                const CBRT_SUB_SCALE: f32 = 16777216.0; // 2^24
+                const CBRT_SUB_UNSCALE: f32 = 0.00390625; // 2^-8
+                const CBRT_HUGE: f32 = 79228162514264337593543950336.0; // 2^96
+                const CBRT_HUGE_UNSCALE: f32 = 4294967296.0; // 2^32
+                const CBRT_BIAS: i32 = 709958130;
+                const CBRT_ITERATIONS: usize = 2;
+
+                #[inline]
+                fn tanh_poly(x: $f) -> $f {
+                    let s = x * x;
+                    ((((splat(-5.70498872745E-3) * s + splat(2.06390887954E-2)) * s
+                       + splat(-5.37397155531E-2)) * s + splat(1.33314422036E-1)) * s
+                     + splat(-3.33332819422E-1)) * s * x + x
+                }
+            }
+            math_impl!($f: $module);
+            )*
+    }
+}
+
+// The bits of 2/π, starting with 64 zero bits before the binary
+// point, for `rem_pio2_large`.
+const TWO_OVER_PI: [u32; 39] = [
+    0x00000000, 0x00000000,
+    0xa2f9836e, 0x4e441529, 0xfc2757d1, 0xf534ddc0,
+    0xdb629599, 0x3c439041, 0xfe5163ab, 0xdebbc561,
+    0xb7246e3a, 0x424dd2e0, 0x06492eea, 0x09d1921c,
+    0xfe1deb1c, 0xb129a73e, 0xe88235f5, 0x2ebb4484,
+    0xe99c7026, 0xb45f7e41, 0x3991d639, 0x835339f4,
+    0x9c845f8b, 0xbdf9283b, 0x1ff897ff, 0xde05980f,
+    0xef2f118b, 0x5a0a6d1f, 0x6d367ecf, 0x27cb09b7,
+    0x4f463f66, 0x9e5fea2d, 0x7527bac7, 0xebe5f17b,
+    0x3d0739f7, 0x8a5292ea, 0x6bfb5fb1, 0x1f8d5d08,
+    0x56033046,
+];
+
+/// `a * b` as `hi + lo` exactly, by Dekker's algorithm.
+#[inline]
+fn two_prod(a: f64, b: f64) -> (f64, f64) {
+    #[inline]
+    fn split(a: f64) -> (f64, f64) {
+        let t = 134217729.0 * a; // 2^27 + 1
+        let hi = t - (t - a);
+        (hi, a - hi)
+    }
+    let p = a * b;
+    let (ah, al) = split(a);
+    let (bh, bl) = split(b);
+    (p, ((ah * bh - p) + ah * bl + al * bh) + al * bl)
+}
+
+/// Reduce a finite `x >= 2^20` to `y0 + y1` in `[-π/4, π/4]` and the
+/// quadrant modulo 4, with the Payne-Hanek method.
+///
+/// This only uses integer arithmetic and IEEE 754 operations, so it
+/// gives the same result everywhere, unlike the platform's `sin`.
+fn rem_pio2_large(x: f64) -> (f64, f64, i64) {
+    const PIO2_HI: f64 = 1.57079632679489655800e+00;
+    const PIO2_LO: f64 = 6.12323399573676603587e-17;
+    let bits: u64 = unsafe { mem::transmute(x) };
+    let e = ((bits >> 52) & 0x7ff) as i32 - 1075;
+    let m = (bits & ((1 << 52) - 1)) | (1 << 52);
+
+    // `x * 2/π = m * 2^e * 2/π`, and the bits of 2/π before `2^-(e -
+    // 1)` only add multiples of 4 to that, so 192 bits of 2/π from
+    // there are enough for the quadrant and 137 bits of the fraction.
+    let start = (e - 1 + 63) as usize;
+    let (word, shift) = (start / 32, start % 32);
+    let mut window = [0u64; 6]; // least significant first
+    for i in 0..6 {
+        let hi = TWO_OVER_PI[word + 5 - i] as u64;
+        let lo = TWO_OVER_PI[word + 6 - i] as u64;
+        window[i] = ((hi << 32 | lo) >> (32 - shift)) & 0xffffffff;
+    }
+    let mut p = [0u32; 8]; // `m * window`, least significant first
+    for (i, &mi) in [m & 0xffffffff, m >> 32].iter().enumerate() {
+        let mut carry = 0;
+        for j in 0..6 {
+            let t = window[j] * mi + p[i + j] as u64 + carry;
+            p[i + j] = t as u32;
+            carry = t >> 32;
+        }
+        p[i + 6] = carry as u32;
+    }
+
+    // `p / 2^190` is now `x * 2/π` modulo 4; round it to the nearest
+    // integer, leaving the fraction in `p[..6]`
+    let mut n = (p[5] >> 30) as i64;
+    p[5] &= 0x3fffffff;
+    let negative = p[5] & 0x20000000 != 0;
+    if negative {
+        n += 1;
+        let mut borrow = 0;
+        for limb in &mut p[..6] {
+            let t = (0u64).wrapping_sub(*limb as u64 + borrow);
+            *limb = t as u32;
+            borrow = (t >> 63) & 1;
+        }
+        p[5] &= 0x3fffffff;
+    }
+
+    // convert the top 128 bits of the fraction to `hi + lo`
+    let top = match p[..6].iter().rposition(|&limb| limb != 0) {
+        Some(top) => top,
+        None => return (0.0, 0.0, n & 3),
+    };
+    let mut scale: f64 = unsafe {
+        mem::transmute(((1023 + 32 * top as i64 - 190) as u64) << 52)
+    };
+    let (mut hi, mut lo) = (0.0, 0.0);
+    for limb in p[top.saturating_sub(3)..top + 1].iter().rev() {
+        let t = *limb as f64 * scale;
+        let s = hi + t;
+        lo += t - (s - hi);
+        hi = s;
+        scale *= 1.0 / 4294967296.0;
+    }
+
+    // and multiply by π/2
+    let (y, y_lo) = two_prod(hi, PIO2_HI);
+    let y_lo = y_lo + (hi * PIO2_LO + lo * PIO2_HI);
+    let y0 = y + y_lo;
+    let y1 = y_lo - (y0 - y);
+    if negative {
+        (-y0, -y1, n & 3)
+    } else {
+        (y0, y1, n & 3)
+    }
+}
+
+macro_rules! f64_math {
+    ($($module: ident: $f: ident, $i: ident, $u: ident, $length: expr;)*) => {
+        $(
+            mod $module {
+                use super::*;
+                use std::f64::{INFINITY as INF, NAN, MIN_POSITIVE};
+
+                helpers!($f, $i, $u, f64, $length, to_i64, to_f64, 52, 1023);
+
+                const PI: f64 = 3.14159265358979323846;
+                const PI_LO: f64 = 1.2246467991473532e-16;
+
+                const LOG2_E: f64 = 1.4426950408889634073599;
+                const LN2_HI: f64 = 6.93147180369123816490e-01;
+                const LN2_LO: f64 = 1.90821492927058770002e-10;
+
+                pub fn exp(x: $f) -> $f {
+                    let big = x.gt(splat(709.782712893383996843));
+                    let small = x.lt(splat(-745.1332191019412));
+                    let nan = x.ne(x);
+                    let x_ = (big | small | nan).select(splat(0.0), x);
+                    let n = round(x_ * splat(LOG2_E));
+                    let r = x_ - n * splat(6.93145751953125E-1) - n * splat(1.42860682030941723212E-6);
+                    let rr = r * r;
+                    let px = r * ((splat(1.26177193074810590878E-4) * rr
+                                   + splat(3.02994407707441961300E-2)) * rr
+                                  + splat(9.99999999999999999910E-1));
+                    let qx = ((splat(3.00198505138664455042E-6) * rr
+                               + splat(2.52448340349684104192E-3)) * rr
+                              + splat(2.27265548208155028766E-1)) * rr
+                        + splat(2.00000000000000000009E0);
+                    let p = splat(1.0) + splat(2.0) * (px / (qx - px));
+                    let y = ldexp(p, n.to_i64());
+                    let y = big.select(splat(INF), y);
+                    let y = small.select(splat(0.0), y);
+                    nan.select(x, y)
+                }
+
+                pub fn exp2(x: $f) -> $f {
+                    let big = x.ge(splat(1024.0));
+                    let small = x.lt(splat(-1075.0));
+                    let nan = x.ne(x);
+                    let x_ = (big | small | nan).select(splat(0.0), x);
+                    let n = round(x_);
+                    let y = exp2_reduced(x_ - n, n);
+                    let y = big.select(splat(INF), y);
+                    let y = small.select(splat(0.0), y);
+                    nan.select(x, y)
+                }
+                /// `2^(r + n)` for `|r| <= 1/2` and an integral `n`.
+                #[inline]
+                fn exp2_reduced(r: $f, n: $f) -> $f {
+                    let rr = r * r;
+                    let px = r * ((splat(2.30933477057345225087E-2) * rr
+                                   + splat(2.02020656693165307700E1)) * rr
+                                  + splat(1.51390680115615096133E3));
+                    let qx = (rr + splat(2.33184211722314911771E2)) * rr
+                        + splat(4.36821166879210612817E3);
+                    let p = splat(1.0) + splat(2.0) * (px / (qx - px));
+                    ldexp(p, n.to_i64())
+                }
+
+                const LOG_SUB_SCALE: f64 = 18014398509481984.0; // 2^54
+                const LOG_SUB_SHIFT: i64 = 54;
+                const ONE_BITS: i64 = 0x3ff00000 << 32;
+                const SQRT_HALF_BITS: i64 = 0x3fe6a09e << 32;
+                const LOG_HI_MASK: u64 = 0xffffffff00000000;
+
+                #[inline]
+                fn log_kernel(f: $f) -> ($f, $f, $f) {
+                    let s = f / (splat(2.0) + f);
+                    let z = s * s;
+                    let w = z * z;
+                    let t1 = w * (splat(3.999999999940941908e-01)
+                                  + w * (splat(2.222219843214978396e-01)
+                                         + w * splat(1.531383769920937332e-01)));
+                    let t2 = z * (splat(6.666666666666735130e-01)
+                                  + w * (splat(2.857142874366239149e-01)
+                                         + w * (splat(1.818357216161805012e-01)
+                                                + w * splat(1.479819860511658591e-01))));
+                    (s, splat(0.5) * f * f, t2 + t1)
+                }
+
+                pub fn log2(x: $f) -> $f {
+                    let (f, dk) = log_reduce(x);
+                    let (hi, lo) = log_hi_lo(f);
+                    let val_hi = hi * splat(1.44269504072144627571e+00);
+                    let val_lo = (lo + hi) * splat(1.67517131648865118353e-10)
+                        + lo * splat(1.44269504072144627571e+00);
+                    let w = dk + val_hi;
+                    let val_lo = val_lo + ((dk - w) + val_hi);
+                    log_special(x, val_lo + w)
+                }
+
+                pub fn log10(x: $f) -> $f {
+                    let (f, dk) = log_reduce(x);
+                    let (hi, lo) = log_hi_lo(f);
+                    let val_hi = hi * splat(4.34294481878168880939e-01);
+                    let y = dk * splat(3.01029995663611771306e-01);
+                    let val_lo = dk * splat(3.69423907715893078616e-13)
+                        + (lo + hi) * splat(2.50829467116452752298e-11)
+                        + lo * splat(4.34294481878168880939e-01);
+                    let w = y + val_hi;
+                    let val_lo = val_lo + ((y - w) + val_hi);
+                    log_special(x, val_lo + w)
+                }
+
+                const TRIG_RANGE: f64 = 1647099.0; // about 2^20 * π/2
+                const INVPIO2: f64 = 6.36619772367581382433e-01;
+                const PIO2_1: f64 = 1.57079632673412561417e+00;
+                const PIO2_2: f64 = 6.07710050630396597660e-11;
+                const PIO2_2T: f64 = 2.02226624879595063154e-21;
+                const PIO2_3: f64 = 2.02226624871116645580e-21;
+                const PIO2_3T: f64 = 8.47842766036889956997e-32;
+
+                /// Reduce `ax` (non-negative, below `TRIG_RANGE`) to
+                /// `y0 + y1` in `[-π/4, π/4]` and the quadrant `n`, like
+                /// fdlibm's `__rem_pio2`, but always taking all three
+                /// steps rather than checking for cancellation.
+                #[inline]
+                fn rem_pio2(ax: $f) -> ($f, $f, $i) {
+                    let n = round(ax * splat(INVPIO2));
+                    let t = ax - n * splat(PIO2_1);
+                    let w = n * splat(PIO2_2);
+                    let r = t - w;
+                    let t = r;
+                    let w = n * splat(PIO2_3);
+                    let r = t - w;
+                    let w = n * splat(PIO2_3T) - ((t - r) - w);
+                    let y0 = r - w;
+                    (y0, (r - y0) - w, n.to_i64())
+                }
+                /// The sign bit to apply for bit 1 of the quadrant `n`.
+                #[inline]
+                fn quadrant_sign(n: $i) -> $u {
+                    bitcast((n & $i::splat(2)) << 62)
+                }
+
+                /// `sin(x + y)` for `|x + y| <= π/4`, where `y` is
+                /// the tail of `x`.
+                #[inline]
+                fn sin_kernel(x: $f, y: $f) -> $f {
+                    let z = x * x;
+                    let w = z * z;
+                    let r = splat(8.33333333332248946124e-03)
+                        + z * (splat(-1.98412698298579493134e-04)
+                               + z * splat(2.75573137070700676789e-06))
+                        + z * w * (splat(-2.50507602534068634195e-08)
+                                   + z * splat(1.58969099521155010221e-10));
+                    let v = z * x;
+                    x - ((z * (splat(0.5) * y - v * r) - y)
+                         - v * splat(-1.66666666666666324348e-01))
+                }
+                /// `cos(x + y)` for `|x + y| <= π/4`, where `y` is
+                /// the tail of `x`.
+                #[inline]
+                fn cos_kernel(x: $f, y: $f) -> $f {
+                    let z = x * x;
+                    let w = z * z;
+                    let r = z * (splat(4.16666666666666019037e-02)
+                                 + z * (splat(-1.38888888888741095749e-03)
+                                        + z * splat(2.48015872894767294178e-05)))
+                        + w * w * (splat(-2.75573143513906633035e-07)
+                                   + z * (splat(2.08757232129817482790e-09)
+                                          + z * splat(-1.13596475577881948265e-11)));
+                    let hz = splat(0.5) * z;
+                    let w = splat(1.0) - hz;
+                    w + (((splat(1.0) - w) - hz) + (z * r - x * y))
+                }
+                /// `tan(x + y)` for `|x + y| <= π/4`, or `-1/tan(x +
+                /// y)` in the `odd` lanes.
+                #[inline]
+                fn tan_kernel(x: $f, y: $f, odd: $i) -> $f {
+                    const T: [f64; 13] = [
+                        3.33333333333334091986e-01, 1.33333333333201242699e-01,
+                        5.39682539762260521377e-02, 2.18694882948595424599e-02,
+                        8.86323982359930005737e-03, 3.59207910759131235356e-03,
+                        1.45620945432529025516e-03, 5.88041240820264096874e-04,
+                        2.46463134818469906812e-04, 7.81794442939557092300e-05,
+                        7.14072491382608190305e-05, -1.85586374855275456654e-05,
+                        2.59073051863633712884e-05,
+                    ];
+                    const PIO4: f64 = 7.85398163397448278999e-01;
+                    const PIO4_LO: f64 = 3.06161699786838301793e-17;
+                    const HI_MASK: u64 = 0xffffffff00000000;
+                    // for |x| >= 0.6744, use tan(x) = tan(π/4 - x')
+                    let big = abs(x).ge(splat(0.6743354797363281));
+                    let flip = sign(x) & big.select($u::splat(!0), $u::splat(0));
+                    let x = big.select((splat(PIO4) - xor_sign(x, flip))
+                                       + (splat(PIO4_LO) - xor_sign(y, flip)),
+                                       x);
+                    let y = big.select(splat(0.0), y);
+                    let z = x * x;
+                    let w = z * z;
+                    let r = splat(T[1]) + w * (splat(T[3]) + w * (splat(T[5])
+                        + w * (splat(T[7]) + w * (splat(T[9]) + w * splat(T[11])))));
+                    let v = z * (splat(T[2]) + w * (splat(T[4]) + w * (splat(T[6])
+                        + w * (splat(T[8]) + w * (splat(T[10]) + w * splat(T[12]))))));
+                    let s = z * x;
+                    let r = y + z * (s * (r + v) + y) + s * splat(T[0]);
+                    let w = x + r;
+                    let odd = odd.ne($i::splat(0));
+
+                    let s = odd.select(splat(-1.0), splat(1.0));
+                    let big_tan = s - splat(2.0) * (x + (r - w * w / (w + s)));
+                    let big_tan = xor_sign(big_tan, flip);
+                    // -1 / (x + r), computed accurately
+                    let w0 = from_bits(bits(w) & $u::splat(HI_MASK));
+                    let v = r - (w0 - x);
+                    let a = splat(-1.0) / w;
+                    let a0 = from_bits(bits(a) & $u::splat(HI_MASK));
+                    let odd_tan = a0 + a * (splat(1.0) + a0 * w0 + a0 * v);
+                    big.select(big_tan, odd.select(odd_tan, w))
+                }
+
+                /// `rem_pio2` for any `ax`, reducing the lanes outside
+                /// `TRIG_RANGE` one at a time with `rem_pio2_large`.
+                /// Infinite and NaN lanes reduce to NaN.
+                ///
+                /// This is only used with `deterministic`; otherwise
+                /// vectors with such lanes go to the scalar functions,
+                /// which are usually faster at it.
+                #[inline]
+                fn rem_pio2_any(ax: $f) -> ($f, $f, $i) {
+                    let small = ax.lt(splat(TRIG_RANGE));
+                    if small.all() {
+                        return rem_pio2(ax);
+                    }
+                    let (mut y0, mut y1, mut n) = rem_pio2(small.select(ax, splat(0.0)));
+                    for i in 0..$length {
+                        let a = ax.extract(i);
+                        if !(a < TRIG_RANGE) {
+                            let (r0, r1, q) = if a < INF {
+                                rem_pio2_large(a)
+                            } else {
+                                (NAN, NAN, 0)
+                            };
+                            y0 = y0.replace(i, r0);
+                            y1 = y1.replace(i, r1);
+                            n = n.replace(i, q);
+                        }
+                    }
+                    (y0, y1, n)
+                }
+
+                #[inline]
+                fn map(x: $f, f: fn(f64) -> f64) -> $f {
+                    let mut ret = x;
+                    for i in 0..$length {
+                        ret = ret.replace(i, f(x.extract(i)));
+                    }
+                    ret
+                }
+                /// Whether `sin`, `cos` and `tan` of `x` should use
+                /// the scalar functions.
+                #[inline]
+                fn trig_scalar(x: $f) -> bool {
+                    !cfg!(feature = "deterministic") && (!abs(x).lt(splat(TRIG_RANGE))).any()
+                }
+
+                pub fn sincos(x: $f) -> ($f, $f) {
+                    if trig_scalar(x) {
+                        return (map(x, f64::sin), map(x, f64::cos));
+                    }
+                    let (y0, y1, n) = rem_pio2_any(abs(x));
+                    let s = sin_kernel(y0, y1);
+                    let c = cos_kernel(y0, y1);
+                    let swap = (n & $i::splat(1)).ne($i::splat(0));
+                    let sin = xor_sign(swap.select(c, s), quadrant_sign(n) ^ sign(x));
+                    let cos = xor_sign(swap.select(s, c), quadrant_sign(n + $i::splat(1)));
+                    (sin, cos)
+                }
+                pub fn sin(x: $f) -> $f {
+                    sincos(x).0
+                }
+                pub fn cos(x: $f) -> $f {
+                    sincos(x).1
+                }
+
+                pub fn tan(x: $f) -> $f {
+                    if trig_scalar(x) {
+                        return map(x, f64::tan);
+                    }
+                    let (y0, y1, n) = rem_pio2_any(abs(x));
+                    xor_sign(tan_kernel(y0, y1, n & $i::splat(1)), sign(x))
+                }
+
+                /// `atan` of a non-negative (or NaN) `ax`.
+                #[inline]
+                fn atan_abs(ax: $f) -> $f {
+                    const MOREBITS: f64 = 6.123233995736765886130E-17;
+                    let big = ax.gt(splat(2.41421356237309504880));
+                    let mid = ax.gt(splat(0.66)) & !big;
+                    let x = big.select(splat(-1.0) / ax,
+                                       mid.select((ax - splat(1.0)) / (ax + splat(1.0)), ax));
+                    let y = big.select(splat(PI / 2.0),
+                                       mid.select(splat(PI / 4.0), splat(0.0)));
+                    let more = big.select(splat(MOREBITS),
+                                          mid.select(splat(0.5 * MOREBITS), splat(0.0)));
+                    let z = x * x;
+                    let p = (((splat(-8.750608600031904122785E-1) * z
+                               + splat(-1.615753718733365076637E1)) * z
+                              + splat(-7.500855792314704667340E1)) * z
+                             + splat(-1.228866684490136173410E2)) * z
+                        + splat(-6.485021904942025371773E1);
+                    let q = ((((z + splat(2.485846490142306297962E1)) * z
+                               + splat(1.650270098316988542046E2)) * z
+                              + splat(4.328810604912902668951E2)) * z
+                             + splat(4.853903996359136964868E2)) * z
+                        + splat(1.945506571482613964425E2);
+                    y + ((x * (z * p / q) + x) + more)
+                }
+
+                /// `exp2(y * log2(|x|))`, with the logarithm and the
+                /// product carried to about twice the precision.
+                pub fn pow(x: $f, y: $f) -> $f {
+                    let ax = abs(x);
+                    let ay = abs(y);
+                    let (lh, ll) = log2_dd(ax);
+                    let (p, p_lo) = two_prod(y, lh);
+                    let p_lo = p_lo + y * ll;
+                    // `p_lo` is NaN if `y * lh` is infinite or `y` is
+                    // huge and `lh` zero
+                    let p_lo = p_lo.eq(p_lo).select(p_lo, splat(0.0));
+                    let th = p + p_lo;
+                    let tl = p_lo - (th - p);
+                    let big = th.gt(splat(1024.0)) | (th.eq(splat(1024.0)) & tl.ge(splat(0.0)));
+                    let small = th.lt(splat(-1075.0));
+                    let out = big | small | th.ne(th);
+                    let th_ = out.select(splat(0.0), th);
+                    let n = round(th_);
+                    let r = exp2_reduced((th_ - n) + out.select(splat(0.0), tl), n);
+                    let r = big.select(splat(INF), small.select(splat(0.0), r));
+
+                    // zero, infinite and NaN lanes
+                    let grows = ax.gt(splat(1.0)) ^ y.lt(splat(0.0));
+                    let r = ay.eq(splat(INF)).select(grows.select(splat(INF), splat(0.0)), r);
+                    let r = ax.eq(splat(0.0)).select(y.lt(splat(0.0)).select(splat(INF), splat(0.0)), r);
+                    let r = ax.eq(splat(INF)).select(y.lt(splat(0.0)).select(splat(0.0), splat(INF)), r);
+                    let r = (x.ne(x) | y.ne(y)).select(x + y, r);
+
+                    // and the sign, as for `f32` lanes
+                    let int = round(y).eq(y);
+                    let y_ = ay.lt(splat(9007199254740992.0)).select(y, splat(0.0));
+                    let odd = int & (y_.to_i64() & $i::splat(1)).eq($i::splat(1));
+                    let r = xor_sign(r, sign(x) & odd.select($u::splat(!0), $u::splat(0)));
+                    let r = (x.lt(splat(0.0)) & x.gt(splat(-INF)) & !int).select(splat(NAN), r);
+                    let r = (ax.eq(splat(1.0)) & ay.eq(splat(INF))).select(splat(1.0), r);
+                    (y.eq(splat(0.0)) | x.eq(splat(1.0))).select(splat(1.0), r)
+                }
+                /// `log2(x)` as `hi + lo`, with a relative error of
+                /// about 2^-63, for positive and finite `x`.
+                ///
+                /// This is `ln(1 + f) = 2 atanh(s)`, where `s = f / (2 +
+                /// f)`, with the leading terms in double-double.
+                #[inline]
+                fn log2_dd(x: $f) -> ($f, $f) {
+                    const TWO_THIRDS_HI: f64 = 0.6666666666666666;
+                    const TWO_THIRDS_LO: f64 = 3.700743415417188e-17;
+                    const LOG2_E_HI: f64 = 1.4426950408889634;
+                    const LOG2_E_LO: f64 = 2.0355273740931033e-17;
+                    let (f, dk) = log_reduce(x);
+                    let (u, u_lo) = two_sum(splat(2.0), f);
+                    let s = f / u;
+                    let (q, q_lo) = two_prod(s, u);
+                    let s_lo = ((f - q) - q_lo - s * u_lo) / u;
+
+                    let (z, z_lo) = two_prod(s, s);
+                    let (s3, s3_lo) = two_prod(s, z);
+                    let s3_lo = s3_lo + s * z_lo + s_lo * z * splat(3.0);
+                    let (t, t_lo) = two_prod(s3, splat(TWO_THIRDS_HI));
+                    let t_lo = t_lo + s3 * splat(TWO_THIRDS_LO) + s3_lo * splat(TWO_THIRDS_HI);
+                    // 2 atanh(s) - 2s - 2s^3/3, to 2^-56
+                    let rest = s3 * z * (splat(0.4) + z * (splat(0.2857142857142937)
+                        + z * (splat(0.22222222221654733) + z * (splat(0.1818181833565416)
+                        + z * (splat(0.15384594934597917) + z * (splat(0.13334806212100603)
+                        + z * (splat(0.11706195689881364) + z * splat(0.11723603622905411))))))));
+                    let (ln, ln_lo) = two_sum(s + s, t);
+                    let ln_lo = ln_lo + (s_lo + s_lo) + (t_lo + rest);
+
+                    let (l, l_lo) = two_prod(ln, splat(LOG2_E_HI));
+                    let l_lo = l_lo + ln * splat(LOG2_E_LO) + ln_lo * splat(LOG2_E_HI);
+                    let (w, w_lo) = two_sum(dk, l);
+                    let w_lo = w_lo + l_lo;
+                    let hi = w + w_lo;
+                    (hi, w_lo - (hi - w))
+                }
+                /// `a + b` as `hi + lo` exactly.
+                #[inline]
+                fn two_sum(a: $f, b: $f) -> ($f, $f) {
+                    let s = a + b;
+                    let t = s - a;
+                    (s, (a - (s - t)) + (b - t))
+                }
+                /// `a * b` as `hi + lo` exactly, by Dekker's algorithm,
+                /// unless it overflows.
+                #[inline]
+                fn two_prod(a: $f, b: $f) -> ($f, $f) {
+                    #[inline]
+                    fn split(a: $f) -> ($f, $f) {
+                        let t = splat(134217729.0) * a; // 2^27 + 1
+                        let hi = t - (t - a);
+                        (hi, a - hi)
+                    }
+                    let p = a * b;
+                    let (ah, al) = split(a);
+                    let (bh, bl) = split(b);
+                    (p, ((ah * bh - p) + ah * bl + al * bh) + al * bl)
+                }
+
+                const CBRT_SUB_SCALE: f64 = 18014398509481984.0; // 2^54
+                const CBRT_SUB_UNSCALE: f64 = 3.814697265625e-06; // 2^-18
+                const CBRT_HUGE: f64 = 9.7453140114e288; // 2^960
+                const CBRT_HUGE_UNSCALE: f64 = 2.13598703592091e96; // 2^320
+                const CBRT_BIAS: i64 = 715094163 << 32;
+                const CBRT_ITERATIONS: usize = 3;
+
+                #[inline]
+                fn tanh_poly(x: $f) -> $f {
+                    let s = x * x;
+                    let p = (splat(-9.64399179425052238628E-1) * s
+                             + splat(-9.92877231001918586564E1)) * s
+                        + splat(-1.61468768441708447952E3);
+                    let q = ((s + splat(1.12811678491632931402E2)) * s
+                             + splat(2.23548839060100448583E3)) * s
+                        + splat(4.84406305325125486048E3);
+                    x + x * s * (p / q)
+                }
+            }
+            math_impl!($f: $module);
+            )*
+    }
+}
+
+macro_rules! math_impl {
+    ($f: ident: $module: ident) => {
+        impl Math for $f {
+            #[inline]
+            fn exp(self) -> Self {
+                $module::exp(self)
+            }
+            #[inline]
+            fn exp2(self) -> Self {
+                $module::exp2(self)
+            }
+            #[inline]
+            fn ln(self) -> Self {
+                $module::ln(self)
+            }
+            #[inline]
+            fn log2(self) -> Self {
+                $module::log2(self)
+            }
+            #[inline]
+            fn log10(self) -> Self {
+                $module::log10(self)
+            }
+            #[inline]
+            fn sin(self) -> Self {
+                $module::sin(self)
+            }
+            #[inline]
+            fn cos(self) -> Self {
+                $module::cos(self)
+            }
+            #[inline]
+            fn sincos(self) -> (Self, Self) {
+                $module::sincos(self)
+            }
+            #[inline]
+            fn tan(self) -> Self {
+                $module::tan(self)
+            }
+            #[inline]
+            fn atan(self) -> Self {
+                $module::atan(self)
+            }
+            #[inline]
+            fn atan2(self, other: Self) -> Self {
+                $module::atan2(self, other)
+            }
+            #[inline]
+            fn pow(self, n: Self) -> Self {
+                $module::pow(self, n)
+            }
+            #[inline]
+            fn cbrt(self) -> Self {
+                $module::cbrt(self)
+            }
+            #[inline]
+            fn tanh(self) -> Self {
+                $module::tanh(self)
+            }
+        }
+    }
+}
+
+// `f32` powers and trigonometric functions are computed on `f64`
+// lanes, which are accurate enough that only the final rounding
+// matters.
+#[inline]
+fn f32x4_sincos(x: f32x4) -> (f32x4, f32x4) {
+    unsafe {
+        let (s, c) = f64x4_math::sincos(simd_cast(x));
+        (simd_cast(s), simd_cast(c))
+    }
+}
+#[inline]
+fn f32x4_tan(x: f32x4) -> f32x4 {
+    unsafe { simd_cast(f64x4_math::tan(simd_cast(x))) }
+}
+#[inline]
+fn f32x4_pow_abs(x: f32x4, y: f32x4) -> f32x4 {
+    unsafe {
+        let x: f64x4 = simd_cast(x);
+        let y: f64x4 = simd_cast(y);
+        simd_cast(f64x4_math::exp2(y * f64x4_math::log2(x)))
+    }
+}
+
+#[inline]
+fn f32x8_join(lo: f32x4, hi: f32x4) -> f32x8 {
+    unsafe { simd_shuffle8(lo, hi, [0, 1, 2, 3, 4, 5, 6, 7]) }
+}
+#[inline]
+fn f32x8_sincos(x: f32x8) -> (f32x8, f32x8) {
+    let (s_lo, c_lo) = f32x4_sincos(x.low());
+    let (s_hi, c_hi) = f32x4_sincos(x.high());
+    (f32x8_join(s_lo, s_hi), f32x8_join(c_lo, c_hi))
+}
+#[inline]
+fn f32x8_tan(x: f32x8) -> f32x8 {
+    f32x8_join(f32x4_tan(x.low()), f32x4_tan(x.high()))
+}
+#[inline]
+fn f32x8_pow_abs(x: f32x8, y: f32x8) -> f32x8 {
+    f32x8_join(f32x4_pow_abs(x.low(), y.low()), f32x4_pow_abs(x.high(), y.high()))
+}
+
+f32_math! {
+    f32x4_math: f32x4, i32x4, u32x4, 4, f32x4_sincos, f32x4_tan, f32x4_pow_abs;
+    f32x8_math: f32x8, i32x8, u32x8, 8, f32x8_sincos, f32x8_tan, f32x8_pow_abs;
+}
+f64_math! {
+    f64x2_math: f64x2, i64x2, u64x2, 2;
+    f64x4_math: f64x4, i64x4, u64x4, 4;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Math;
+    use f32x4;
+    use sixty_four::f64x2;
+    use v256::{f32x8, f64x4};
+    use std::{f32, f64, mem};
+
+    // The distance between `a` and `b` in units in the last place.
+    // NaNs only match NaNs, and zeros only match zeros of the same
+    // sign.
+    fn ulps32(a: f32, b: f32) -> u64 {
+        fn key(x: f32) -> i64 {
+            let i = unsafe { mem::transmute::<f32, i32>(x) } as i64;
+            if i < 0 { i32::min_value() as i64 - i } else { i }
+        }
+        if a.is_nan() || b.is_nan() {
+            return if a.is_nan() && b.is_nan() { 0 } else { u64::max_value() };
+        }
+        if a == 0.0 && b == 0.0 && a.is_sign_negative() != b.is_sign_negative() {
+            return u64::max_value();
+        }
+        (key(a) - key(b)).abs() as u64
+    }
+    fn ulps64(a: f64, b: f64) -> u64 {
+        fn key(x: f64) -> i64 {
+            let i = unsafe { mem::transmute::<f64, i64>(x) };
+            if i < 0 { i64::min_value().wrapping_sub(i) } else { i }
+        }
+        if a.is_nan() || b.is_nan() {
+            return if a.is_nan() && b.is_nan() { 0 } else { u64::max_value() };
+        }
+        if a == 0.0 && b == 0.0 && a.is_sign_negative() != b.is_sign_negative() {
+            return u64::max_value();
+        }
+        let (a, b) = (key(a), key(b));
+        if (a < 0) == (b < 0) {
+            (a - b).abs() as u64
+        } else {
+            a.abs() as u64 + b.abs() as u64
+        }
+    }
+
+    // Special values, then `±2^k * (1 + j/8)` for every exponent,
+    // which covers the subnormals and the arguments that need the
+    // large trigonometric reduction.
+    fn inputs32() -> Vec<f32> {
+        let mut v = vec![0.0, -0.0, f32::INFINITY, -f32::INFINITY, f32::NAN,
+                         f32::MIN_POSITIVE, f32::MIN_POSITIVE / 2.0, 1e-45, -1e-45,
+                         f32::MAX, -f32::MAX, 1.0, -1.0, 0.5, 3.14159265, 1.5707964,
+                         1e5, 1e10, 3e38];
+        for k in -149..128 {
+            let p = (k as f64).exp2();
+            for j in 0..8 {
+                let x = (p * (1.0 + j as f64 / 8.0)) as f32;
+                v.push(x);
+                v.push(-x);
+            }
+        }
+        v
+    }
+    fn inputs64() -> Vec<f64> {
+        let mut v = vec![0.0, -0.0, f64::INFINITY, -f64::INFINITY, f64::NAN,
+                         f64::MIN_POSITIVE, f64::MIN_POSITIVE / 2.0, 5e-324, -5e-324,
+                         f64::MAX, -f64::MAX, 1.0, -1.0, 0.5, f64::consts::PI,
+                         f64::consts::FRAC_PI_2, 1e6, 1e15, 1e22, 1e300];
+        for k in -1074..1024 {
+            let p = (k as f64).exp2();
+            for j in 0..8 {
+                let x = p * (1.0 + j as f64 / 8.0);
+                v.push(x);
+                v.push(-x);
+            }
+        }
+        v
+    }
+    // Pairs for the functions of two arguments, from a coarser grid.
+    fn pairs<T: Copy>(v: &[T], step: usize) -> (Vec<T>, Vec<T>) {
+        let coarse: Vec<T> = v.iter().enumerate()
+            .filter(|&(i, _)| i % step == 0).map(|(_, &x)| x).collect();
+        let mut x = vec![];
+        let mut y = vec![];
+        for &a in &coarse {
+            for &b in &coarse {
+                x.push(a);
+                y.push(b);
+            }
+        }
+        (x, y)
+    }
+
+    fn check32<F4, F8, S>(name: &str, bound: u64, xs: &[f32], ys: &[f32],
+                          f4: F4, f8: F8, scalar: S)
+        where F4: Fn(f32x4, f32x4) -> f32x4, F8: Fn(f32x8, f32x8) -> f32x8, S: Fn(f64, f64) -> f64
+    {
+        let (mut xs, mut ys) = (xs.to_vec(), ys.to_vec());
+        while xs.len() % 8 != 0 {
+            xs.push(1.0);
+            ys.push(1.0);
+        }
+        let (xs, ys) = (&xs[..], &ys[..]);
+        let mut i = 0;
+        while i + 8 <= xs.len() {
+            let r4 = [f4(f32x4::load(xs, i), f32x4::load(ys, i)),
+                      f4(f32x4::load(xs, i + 4), f32x4::load(ys, i + 4))];
+            let r8 = f8(f32x8::load(xs, i), f32x8::load(ys, i));
+            for j in 0..8 {
+                let (x, y) = (xs[i + j], ys[i + j]);
+                let want = scalar(x as f64, y as f64) as f32;
+                let got = r4[j / 4].extract(j as u32 % 4);
+                assert!(ulps32(got, want) <= bound,
+                        "{}({:e}, {:e}) = {:e}, expected {:e}", name, x, y, got, want);
+                let got8 = r8.extract(j as u32);
+                assert!(ulps32(got8, got) == 0,
+                        "{} on f32x8 ({:e}, {:e}) = {:e}, but {:e} on f32x4", name, x, y, got8, got);
+            }
+            i += 8;
+        }
+    }
+    fn check64<F2, F4, S>(name: &str, bound: u64, xs: &[f64], ys: &[f64],
+                          f2: F2, f4: F4, scalar: S)
+        where F2: Fn(f64x2, f64x2) -> f64x2, F4: Fn(f64x4, f64x4) -> f64x4, S: Fn(f64, f64) -> f64
+    {
+        let (mut xs, mut ys) = (xs.to_vec(), ys.to_vec());
+        while xs.len() % 4 != 0 {
+            xs.push(1.0);
+            ys.push(1.0);
+        }
+        let (xs, ys) = (&xs[..], &ys[..]);
+        let mut i = 0;
+        while i + 4 <= xs.len() {
+            let r2 = [f2(f64x2::load(xs, i), f64x2::load(ys, i)),
+                      f2(f64x2::load(xs, i + 2), f64x2::load(ys, i + 2))];
+            let r4 = f4(f64x4::load(xs, i), f64x4::load(ys, i));
+            for j in 0..4 {
+                let (x, y) = (xs[i + j], ys[i + j]);
+                let want = scalar(x, y);
+                let got = r2[j / 2].extract(j as u32 % 2);
+                assert!(ulps64(got, want) <= bound,
+                        "{}({:e}, {:e}) = {:e}, expected {:e}", name, x, y, got, want);
+                let got4 = r4.extract(j as u32);
+                assert!(ulps64(got4, got) == 0,
+                        "{} on f64x4 ({:e}, {:e}) = {:e}, but {:e} on f64x2", name, x, y, got4, got);
+            }
+            i += 4;
+        }
+    }
+
+    // The functions of one argument, with the bounds from the table
+    // in the module documentation.
+    macro_rules! unary {
+        ($($test: ident: $method: ident ($reference: expr) $bound32: expr, $bound64: expr;)*) => {
+            $(#[test]
+            fn $test() {
+                let xs = inputs32();
+                check32(stringify!($method), $bound32, &xs, &xs,
+                        |x, _| x.$method(), |x, _| x.$method(), |x, _| $reference(x));
+                let xs = inputs64();
+                check64(stringify!($method), $bound64, &xs, &xs,
+                        |x, _| x.$method(), |x, _| x.$method(), |x, _| $reference(x));
+            })*
+        }
+    }
+    unary! {
+        exp: exp (f64::exp) 1, 2;
+        exp2: exp2 (f64::exp2) 2, 1;
+        ln: ln (f64::ln) 1, 1;
+        log2: log2 (f64::log2) 1, 1;
+        log10: log10 (f64::log10) 1, 2;
+        sin: sin (f64::sin) 1, 1;
+        cos: cos (f64::cos) 1, 1;
+        tan: tan (f64::tan) 1, 2;
+        atan: atan (f64::atan) 3, 1;
+        cbrt: cbrt (f64::cbrt) 1, 1;
+        tanh: tanh (f64::tanh) 2, 2;
+    }
+
+    #[test]
+    fn sincos() {
+        let xs = inputs32();
+        check32("sincos.0", 1, &xs, &xs,
+                |x, _| x.sincos().0, |x, _| x.sincos().0, |x, _| x.sin());
+        check32("sincos.1", 1, &xs, &xs,
+                |x, _| x.sincos().1, |x, _| x.sincos().1, |x, _| x.cos());
+        let xs = inputs64();
+        check64("sincos.0", 1, &xs, &xs,
+                |x, _| x.sincos().0, |x, _| x.sincos().0, |x, _| x.sin());
+        check64("sincos.1", 1, &xs, &xs,
+                |x, _| x.sincos().1, |x, _| x.sincos().1, |x, _| x.cos());
+    }
+
+    // only used by default with `deterministic`, so check it directly
+    #[test]
+    fn rem_pio2_large() {
+        for &x in &inputs64() {
+            if !(x >= 1048576.0 && x < f64::INFINITY) {
+                continue;
+            }
+            let (y0, y1, n) = super::rem_pio2_large(x);
+            assert!(y0.abs() <= f64::consts::FRAC_PI_4 * (1.0 + f64::EPSILON), "{:e}", x);
+            assert_eq!(y0 + y1, y0, "{:e}", x);
+            let r = match n {
+                0 => y0.sin(),
+                1 => y0.cos(),
+                2 => -y0.sin(),
+                _ => -y0.cos(),
+            };
+            // `y0` alone is within an ULP of `x` modulo π/2
+            assert!(ulps64(r, x.sin()) <= 2, "sin({:e}) = {:e}, not {:e}", x, x.sin(), r);
+        }
+    }
+
+    #[test]
+    fn atan2() {
+        let (ys, xs) = pairs(&inputs32(), 37);
+        check32("atan2", 4, &ys, &xs,
+                |y, x| y.atan2(x), |y, x| y.atan2(x), |y, x| y.atan2(x));
+        let (ys, xs) = pairs(&inputs64(), 173);
+        check64("atan2", 2, &ys, &xs,
+                |y, x| y.atan2(x), |y, x| y.atan2(x), |y, x| y.atan2(x));
+    }
+
+    #[test]
+    fn pow() {
+        // exponents where the sign, overflow and underflow matter,
+        // with every input as the base
+        let exponents32 = [0.0, -0.0, 1.0, -1.0, 0.5, -0.5, 2.0, 3.0, -3.0,
+                           127.5, -149.5, 1e10, -1e10, f32::INFINITY,
+                           -f32::INFINITY, f32::NAN];
+        let exponents64 = [0.0, -0.0, 1.0, -1.0, 0.5, -0.5, 2.0, 3.0, -3.0,
+                           1023.5, -1074.5, 1e300, -1e300, f64::INFINITY,
+                           -f64::INFINITY, f64::NAN];
+
+        let (mut xs, mut ys) = pairs(&inputs32(), 29);
+        for &x in &inputs32() {
+            for &y in &exponents32 {
+                xs.push(x);
+                ys.push(y);
+            }
+        }
+        check32("pow", 1, &xs, &ys,
+                |x, y| x.pow(y), |x, y| x.pow(y), |x, y| x.powf(y));
+
+        let (mut xs, mut ys) = pairs(&inputs64(), 131);
+        for &x in &inputs64() {
+            for &y in &exponents64 {
+                xs.push(x);
+                ys.push(y);
+            }
+        }
+        check64("pow", 1, &xs, &ys,
+                |x, y| x.pow(y), |x, y| x.pow(y), |x, y| x.powf(y));
+    }
+}
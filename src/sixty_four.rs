@@ -9,19 +9,18 @@ use super::{
     simd_cast,
     simd_add, simd_sub, simd_mul, simd_div, simd_shl, simd_shr, simd_and, simd_or, simd_xor,
 
-    Unalign, bitcast,
+    Unalign, bitcast, fmt_lanes, parse_lanes,
 };
 use std::mem;
 use std::ops;
 
-/// Boolean type for 64-bit integers.
+/// Boolean type for 64-bit integers and floats.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Copy, Clone)]
 pub struct bool64i(i64);
 /// Boolean type for 64-bit floats.
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug, Copy, Clone)]
-pub struct bool64f(i64);
+#[deprecated(note = "use `bool64i`, which is shared by integers and floats")]
+pub type bool64f = bool64i;
 /// A SIMD vector of 2 `u64`s.
 #[repr(simd)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -37,25 +36,113 @@ pub struct i64x2(i64, i64);
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Copy)]
 pub struct f64x2(f64, f64);
-/// A SIMD boolean vector for length-2 vectors of 64-bit integers.
+/// A SIMD boolean vector for length-2 vectors of 64-bit integers
+/// or floats.
 #[repr(simd)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Copy)]
 pub struct bool64ix2(i64, i64);
 /// A SIMD boolean vector for length-2 vectors of 64-bit floats.
-#[repr(simd)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug, Copy)]
-pub struct bool64fx2(i64, i64);
+#[deprecated(note = "use `bool64ix2`, which works with `f64x2` too")]
+pub type bool64fx2 = bool64ix2;
 
 simd! {
-    bool64ix2: i64x2 = i64, u64x2 = u64, bool64ix2 = bool64i;
-    bool64fx2: f64x2 = f64, bool64fx2 = bool64f;
+    bool64ix2: i64x2 = i64, u64x2 = u64, f64x2 = f64, bool64ix2 = bool64i;
 }
 basic_impls! {
     u64x2: u64, bool64ix2, simd_shuffle2, 2, x0 | x1;
     i64x2: i64, bool64ix2, simd_shuffle2, 2, x0 | x1;
-    f64x2: f64, bool64fx2, simd_shuffle2, 2, x0 | x1;
+    f64x2: f64, bool64ix2, simd_shuffle2, 2, x0 | x1;
+}
+int_trait_impls! {
+    u64x2: u64, 2;
+    i64x2: i64, 2;
+}
+byte_order_impls! {
+    u64x2: u64x2, 16;
+    i64x2: u64x2, 16;
+    f64x2: u64x2, 16;
+}
+bit_reversal_impls! {
+    u64x2: u8x16, u64x2_swap_bytes;
+    i64x2: u8x16, u64x2_swap_bytes;
+}
+
+#[cfg(test)]
+mod bit_reversal_tests {
+    use super::*;
+
+    #[test]
+    fn u64x2_bit_reversal() {
+        check_bit_reversal!(swap_bytes: u64x2, u64x2, u64, 2);
+        check_bit_reversal!(reverse_bits: u64x2, u64x2, u64, 2);
+    }
+    #[test]
+    fn i64x2_bit_reversal() {
+        check_bit_reversal!(swap_bytes: i64x2, u64x2, u64, 2);
+        check_bit_reversal!(reverse_bits: i64x2, u64x2, u64, 2);
+    }
+}
+
+#[cfg(test)]
+mod byte_order_tests {
+    use super::*;
+
+    #[test]
+    fn u64x2_byte_order() {
+        check_byte_order!(u64x2, u64x2, 16, 8);
+    }
+    #[test]
+    fn i64x2_byte_order() {
+        check_byte_order!(i64x2, u64x2, 16, 8);
+    }
+    #[test]
+    fn f64x2_byte_order() {
+        check_byte_order!(f64x2, u64x2, 16, 8);
+    }
+}
+
+#[cfg(test)]
+mod format_tests {
+    use super::*;
+
+    #[test]
+    fn i64x2_display() {
+        check_display!(i64x2, i64, 2, [0, -9223372036854775808, 9223372036854775807]);
+    }
+    #[test]
+    fn f64x2_display() {
+        check_display!(f64x2, f64, 2, [0.1, -0.0, 1e300, 5e-324, 1.0 / 3.0, ::std::f64::INFINITY,
+                                       ::std::f64::NAN]);
+    }
+}
+
+#[cfg(test)]
+mod masked_op_tests {
+    use super::*;
+
+    #[test]
+    fn f64x2_masked_ops() {
+        check_masked_ops!(f64x2, f64, bool64ix2, 2);
+    }
+    #[test]
+    fn i64x2_masked_ops() {
+        check_masked_ops!(i64x2, i64, bool64ix2, 2);
+    }
+}
+
+#[cfg(test)]
+mod iter_trait_tests {
+    use super::*;
+
+    #[test]
+    fn f64x2_iter_traits() {
+        check_iter_traits!(f64x2, f64, 2);
+    }
+    #[test]
+    fn i64x2_iter_traits() {
+        check_iter_traits!(i64x2, i64, 2);
+    }
 }
 
 mod common {
@@ -69,24 +156,91 @@ mod common {
     pub fn bool64ix2_any(x: bool64ix2) -> bool {
         x.0 != 0 || x.1 != 0
     }
+
+    #[cfg(any(target_arch = "x86",
+              target_arch = "x86_64"))]
+    pub use x86::sse2::common::{f64x2_min_num, f64x2_max_num};
+    #[cfg(target_arch = "aarch64")]
+    pub use aarch64::neon::common::{f64x2_min_num, f64x2_max_num};
+    // 32-bit ARM has no 64-bit float vectors
+    #[cfg(not(any(target_arch = "x86",
+                  target_arch = "x86_64",
+                  target_arch = "aarch64")))]
     #[inline]
-    pub fn bool64fx2_all(x: bool64fx2) -> bool {
-        x.0 != 0 && x.1 != 0
+    pub fn f64x2_min_num(x: f64x2, y: f64x2) -> f64x2 {
+        min_num_lanes!(x, y)
     }
+    #[cfg(not(any(target_arch = "x86",
+                  target_arch = "x86_64",
+                  target_arch = "aarch64")))]
     #[inline]
-    pub fn bool64fx2_any(x: bool64fx2) -> bool {
-        x.0 != 0 || x.1 != 0
-    }}
+    pub fn f64x2_max_num(x: f64x2, y: f64x2) -> f64x2 {
+        max_num_lanes!(x, y)
+    }
+
+    #[inline]
+    pub fn bool64ix2_bitmask(x: bool64ix2) -> u64 {
+        (x.0 as u64 >> 63) | (x.1 as u64 >> 63) << 1
+    }
+
+    #[cfg(all(any(target_arch = "x86",
+                  target_arch = "x86_64"),
+              target_feature = "sse4.1"))]
+    pub use x86::sse4_1::common::bool64ix2_select;
+    #[cfg(not(all(any(target_arch = "x86",
+                      target_arch = "x86_64"),
+                  target_feature = "sse4.1")))]
+    bitwise_selects! {
+        bool64ix2: bool64ix2_select(i64x2);
+    }
+
+    #[cfg(all(any(target_arch = "x86",
+                  target_arch = "x86_64"),
+              target_feature = "ssse3"))]
+    pub use x86::ssse3::common::u64x2_swap_bytes;
+    #[cfg(target_arch = "arm")]
+    pub use arm::neon::common::u64x2_swap_bytes;
+    #[cfg(target_arch = "aarch64")]
+    pub use aarch64::neon::common::u64x2_swap_bytes;
+    #[cfg(not(any(all(any(target_arch = "x86",
+                          target_arch = "x86_64"),
+                      target_feature = "ssse3"),
+                  target_arch = "arm",
+                  target_arch = "aarch64")))]
+    bit_group_swaps! {
+        u64x2_swap_bytes(u64x2) [8 => 0x00ff00ff00ff00ff, 16 => 0x0000ffff0000ffff, 32 => 0x00000000ffffffff];
+    }
+}
 bool_impls! {
-    bool64ix2: bool64i, i64x2, i64, 2, bool64ix2_all, bool64ix2_any, x0 | x1
+    bool64ix2: bool64i, i64x2, i64, 2, bool64ix2_all, bool64ix2_any, bool64ix2_bitmask, bool64ix2_select, x0 | x1
         [/// Convert `self` to a boolean vector for interacting with floating point vectors.
-         to_f -> bool64fx2];
-
-    bool64fx2: bool64f, i64x2, i64, 2, bool64fx2_all, bool64fx2_any, x0 | x1
-        [/// Convert `self` to a boolean vector for interacting with integer vectors.
+         #[deprecated(note = "`bool64ix2` works with floating point vectors directly")]
+         to_f -> bool64ix2,
+         /// Convert `self` to a boolean vector for interacting with integer vectors.
+         #[deprecated(note = "`bool64ix2` works with integer vectors directly")]
          to_i -> bool64ix2];
 }
 
+#[cfg(test)]
+mod bitmask_tests {
+    use super::*;
+
+    #[test]
+    fn bool64ix2_bitmask() {
+        check_bitmask!(bool64ix2, 2);
+    }
+}
+
+#[cfg(test)]
+mod lane_query_tests {
+    use super::*;
+
+    #[test]
+    fn bool64ix2_lane_queries() {
+        check_lane_queries!(bool64ix2, 2);
+    }
+}
+
 impl u64x2 {
     /// Convert each lane to a signed integer.
     #[inline]
@@ -113,17 +267,37 @@ impl i64x2 {
 }
 impl f64x2 {
     /// Convert each lane to a signed integer.
+    ///
+    /// NaN and out of range lanes give platform dependent results,
+    /// unless the `deterministic` feature is enabled, when this is
+    /// the same as `to_i64_saturating`.
     #[inline]
     pub fn to_i64(self) -> i64x2 {
-        unsafe {simd_cast(self)}
+        if cfg!(feature = "deterministic") {
+            self.to_i64_saturating()
+        } else {
+            unsafe {simd_cast(self)}
+        }
     }
     /// Convert each lane to an unsigned integer.
+    ///
+    /// NaN and out of range lanes give platform dependent results,
+    /// unless the `deterministic` feature is enabled, when this is
+    /// the same as `to_u64_saturating`.
     #[inline]
     pub fn to_u64(self) -> u64x2 {
-        unsafe {simd_cast(self)}
+        if cfg!(feature = "deterministic") {
+            self.to_u64_saturating()
+        } else {
+            unsafe {simd_cast(self)}
+        }
     }
 
-    /// Convert each lane to a 32-bit float.
+    /// Convert each lane to a 32-bit float, in the low two lanes of
+    /// the result.
+    ///
+    /// The high two lanes are zero; use `f64x4::narrow` to convert
+    /// four lanes at once.
     #[inline]
     pub fn to_f32(self) -> f32x4 {
         unsafe {
@@ -141,6 +315,87 @@ neg_impls! {
     0.0,
     f64x2,
 }
+fma_impls! {
+    f64x2: f64x2_mul_add, f64x2_mul_sub, f64x2_neg_mul_add;
+}
+#[cfg(test)]
+mod fma_tests {
+    use super::*;
+
+    #[test]
+    fn f64x2_fma() {
+        check_fma!(f64x2, f64, 2);
+    }
+}
+
+float_bit_impls! {
+    f64x2: u64x2;
+}
+#[cfg(test)]
+mod float_bit_tests {
+    use super::*;
+
+    #[test]
+    fn f64x2_float_bits() {
+        check_float_bits!(f64x2, f64, u64, 2);
+    }
+}
+
+float_class_impls! {
+    f64x2: f64, bool64ix2;
+}
+#[cfg(test)]
+mod float_class_tests {
+    use super::*;
+
+    #[test]
+    fn f64x2_float_class() {
+        check_float_class!(f64x2, f64, 2);
+    }
+}
+min_max_impls! {
+    f64x2: f64x2_min_num, f64x2_max_num;
+}
+#[cfg(test)]
+mod min_max_tests {
+    use super::*;
+
+    #[test]
+    fn f64x2_min_max() {
+        check_min_max!(f64x2, f64, 2);
+    }
+}
+f64_approx_impls! {
+    f64x2: u64x2;
+}
+refine_impls! {
+    f64x2,
+}
+#[cfg(test)]
+mod refine_tests {
+    use super::*;
+
+    #[test]
+    fn f64x2_rsqrt_precise1() {
+        check_refine!(f64x2, f64, 2, rsqrt_precise1, -1074..1024,
+                      17, 1, |b| 2 * b - 1, 51, |x: f64| 1.0 / x.sqrt());
+    }
+    #[test]
+    fn f64x2_rsqrt_precise2() {
+        check_refine!(f64x2, f64, 2, rsqrt_precise2, -1074..1024,
+                      17, 2, |b| 2 * b - 1, 51, |x: f64| 1.0 / x.sqrt());
+    }
+    #[test]
+    fn f64x2_recip_precise1() {
+        check_refine!(f64x2, f64, 2, recip_precise1, -1022..1022,
+                      17, 1, |b| 2 * b, 51, |x: f64| 1.0 / x);
+    }
+    #[test]
+    fn f64x2_recip_precise2() {
+        check_refine!(f64x2, f64, 2, recip_precise2, -1022..1022,
+                      17, 2, |b| 2 * b, 51, |x: f64| 1.0 / x);
+    }
+}
 macro_rules! not_impls {
     ($($ty: ident,)*) => {
         $(impl ops::Not for $ty {
@@ -183,16 +438,105 @@ operators! {
 
     BitAnd (simd_and, bitand):
         i64x2, u64x2,
-        bool64ix2,
-        bool64fx2;
+        bool64ix2;
     BitOr (simd_or, bitor):
         i64x2, u64x2,
-        bool64ix2,
-        bool64fx2;
+        bool64ix2;
     BitXor (simd_xor, bitxor):
         i64x2, u64x2,
-        bool64ix2,
-        bool64fx2;
+        bool64ix2;
+}
+
+float_to_int! {
+    f64x2 (i64x2, 4503599627370496.0) {
+        i64x2, i64, bool64ix2:
+            to_i64_saturating, to_i64_round, to_i64_floor, try_to_i64,
+            ge -9223372036854775808.0, 9223372036854775808.0;
+        u64x2, u64, bool64ix2:
+            to_u64_saturating, to_u64_round, to_u64_floor, try_to_u64,
+            gt -1.0, 18446744073709551616.0;
+    }
+}
+#[cfg(test)]
+mod float_to_int_tests {
+    use super::*;
+
+    #[test]
+    fn f64x2_to_i64() {
+        check_float_to_int!(f64x2, f64, i64, 2,
+                            to_i64_saturating, to_i64_round, to_i64_floor, try_to_i64);
+    }
+    #[test]
+    fn f64x2_to_u64() {
+        check_float_to_int!(f64x2, f64, u64, 2,
+                            to_u64_saturating, to_u64_round, to_u64_floor, try_to_u64);
+    }
+}
+widening_mul_impls! {
+    u64x2: split u64x2, false;
+    i64x2: split u64x2, true;
+}
+#[cfg(test)]
+mod widening_mul_tests {
+    use super::*;
+
+    #[test]
+    fn u64x2_widening_mul() {
+        check_widening_mul_64!(u64x2, u64, mul_u64, 2);
+    }
+    #[test]
+    fn i64x2_widening_mul() {
+        check_widening_mul_64!(i64x2, i64, mul_i64, 2);
+    }
+}
+int_div_impls! {
+    i64x2, 2;
+    u64x2, 2;
+}
+operator_forms! {
+    Add, AddAssign (add, add_assign):
+        i64x2: i64, u64x2: u64, f64x2: f64;
+    Sub, SubAssign (sub, sub_assign):
+        i64x2: i64, u64x2: u64, f64x2: f64;
+    Mul, MulAssign (mul, mul_assign):
+        i64x2: i64, u64x2: u64, f64x2: f64;
+    Div, DivAssign (div, div_assign):
+        i64x2: i64, u64x2: u64, f64x2: f64;
+    Rem, RemAssign (rem, rem_assign):
+        i64x2: i64, u64x2: u64;
+
+    BitAnd, BitAndAssign (bitand, bitand_assign):
+        i64x2: i64, u64x2: u64,
+        f64x2: f64,
+        bool64ix2: bool;
+    BitOr, BitOrAssign (bitor, bitor_assign):
+        i64x2: i64, u64x2: u64,
+        f64x2: f64,
+        bool64ix2: bool;
+    BitXor, BitXorAssign (bitxor, bitxor_assign):
+        i64x2: i64, u64x2: u64,
+        f64x2: f64,
+        bool64ix2: bool;
+}
+
+#[cfg(test)]
+mod operator_form_tests {
+    use super::*;
+
+    #[test]
+    fn f64x2_operator_forms() {
+        check_operator_forms!(f64x2, f64, 2, 7.5, -2.0;
+                              + +=, - -=, * *=, / /=, & &=, | |=, ^ ^=);
+    }
+    #[test]
+    fn i64x2_operator_forms() {
+        check_operator_forms!(i64x2, i64, 2, -7, 3;
+                              + +=, - -=, * *=, / /=, % %=, & &=, | |=, ^ ^=);
+    }
+    #[test]
+    fn bool64ix2_operator_forms() {
+        check_operator_forms!(bool64ix2, bool, 2, true, false; & &=, | |=, ^ ^=);
+    }
 }
 
 macro_rules! shift_one { ($ty: ident, $($by: ident),*) => {
@@ -210,6 +554,18 @@ macro_rules! shift_one { ($ty: ident, $($by: ident),*) => {
             fn shr(self, other: $by) -> Self {
                 unsafe {simd_shr(self, $ty::splat(other as <$ty as Simd>::Elem))}
             }
+        }
+        impl ops::ShlAssign<$by> for $ty {
+            #[inline]
+            fn shl_assign(&mut self, other: $by) {
+                *self = *self << other;
+            }
+        }
+        impl ops::ShrAssign<$by> for $ty {
+            #[inline]
+            fn shr_assign(&mut self, other: $by) {
+                *self = *self >> other;
+            }
         }
             )*
     }
@@ -227,3 +583,21 @@ macro_rules! shift {
 shift! {
     i64x2, u64x2
 }
+vector_shifts! {
+    i64x2 (u64x2, 64): i64x2, u64x2;
+    u64x2 (u64x2, 64): u64x2;
+}
+
+#[cfg(test)]
+mod shift_tests {
+    use super::*;
+
+    #[test]
+    fn i64x2_shifts() {
+        check_shifts!(i64x2, i64, u64x2, u64, 2);
+    }
+    #[test]
+    fn u64x2_shifts() {
+        check_shifts!(u64x2, u64, u64x2, u64, 2);
+    }
+}
@@ -0,0 +1,107 @@
+use v256::{u8x32, i8x32, u16x16, i16x16, u32x8, i32x8, u64x4, i64x4, f64x4, f32x8};
+
+/// Sums of slices, computed with SIMD vectors.
+///
+/// ```rust,ignore
+/// use simd::SimdSum;
+/// let total = data.simd_sum();
+/// ```
+///
+/// The elements are added into a 256-bit vector of partial sums,
+/// which is a single register with AVX and a pair of 128-bit ones
+/// otherwise, so the additions happen in the same order on every
+/// platform and float sums are the same everywhere (including with
+/// the `deterministic` feature). The partial sums are then added in
+/// lane order, followed by the elements left over at the end of the
+/// slice.
+///
+/// This order differs from that of `Iterator::sum`, so a float total
+/// may differ slightly from the sequential one. Integer sums wrap on
+/// overflow.
+pub trait SimdSum {
+    /// The type of the total.
+    type Output;
+
+    /// Add up all the elements of `self`.
+    fn simd_sum(&self) -> Self::Output;
+}
+
+macro_rules! simd_sums {
+    ($($elem: ident: $vector: ident, $length: expr, $add: expr;)*) => {
+        $(impl SimdSum for [$elem] {
+            type Output = $elem;
+
+            #[inline]
+            fn simd_sum(&self) -> $elem {
+                let mut sums = $vector::splat(0 as $elem);
+                let mut i = 0;
+                while i + $length <= self.len() {
+                    sums = sums + $vector::load(self, i);
+                    i += $length;
+                }
+
+                let mut total = 0 as $elem;
+                for lane in 0..$length {
+                    total = $add(total, sums.extract(lane));
+                }
+                for &x in &self[i..] {
+                    total = $add(total, x);
+                }
+                total
+            }
+        })*
+    }
+}
+
+simd_sums! {
+    u8: u8x32, 32, u8::wrapping_add;
+    i8: i8x32, 32, i8::wrapping_add;
+    u16: u16x16, 16, u16::wrapping_add;
+    i16: i16x16, 16, i16::wrapping_add;
+    u32: u32x8, 8, u32::wrapping_add;
+    i32: i32x8, 8, i32::wrapping_add;
+    u64: u64x4, 4, u64::wrapping_add;
+    i64: i64x4, 4, i64::wrapping_add;
+    f32: f32x8, 8, |a: f32, b: f32| a + b;
+    f64: f64x4, 4, |a: f64, b: f64| a + b;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SimdSum;
+
+    #[test]
+    fn ints() {
+        // lengths around the vector length, to cover the leftovers
+        for n in 0..70 {
+            let xs: Vec<u8> = (0..n).map(|i| (i * 37) as u8).collect();
+            let want = xs.iter().fold(0u8, |a, &x| a.wrapping_add(x));
+            assert_eq!(xs.simd_sum(), want, "{}", n);
+
+            let xs: Vec<i64> = (0..n).map(|i| i as i64 * -1_000_000_007).collect();
+            assert_eq!(xs.simd_sum(), xs.iter().sum::<i64>(), "{}", n);
+        }
+        // and overflow wraps
+        assert_eq!([i32::max_value(); 9].simd_sum(), i32::max_value().wrapping_mul(9));
+    }
+
+    #[test]
+    fn floats() {
+        // sums of small integers are exact in any order
+        for n in 0..40 {
+            let xs: Vec<f32> = (0..n).map(|i| i as f32 - 7.0).collect();
+            assert_eq!(xs.simd_sum(), xs.iter().sum::<f32>(), "{}", n);
+            let xs: Vec<f64> = (0..n).map(|i| i as f64 * 0.5).collect();
+            assert_eq!(xs.simd_sum(), xs.iter().sum::<f64>(), "{}", n);
+        }
+
+        // otherwise the order shows: here the two big elements meet
+        // in the same lane and cancel, while a sequential sum loses
+        // the ones added to the first
+        let mut xs = vec![1.0f32; 17];
+        xs[0] = 1e8;
+        xs[8] = -1e8;
+        assert_eq!(xs.simd_sum(), 15.0);
+        assert_eq!(xs.iter().sum::<f32>(), 8.0);
+    }
+}
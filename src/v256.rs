@@ -5,14 +5,14 @@ use std::mem;
 use super::{
 	Simd,
     u32x4, i32x4, u16x8, i16x8, u8x16, i8x16, f32x4,
-    bool32ix4, bool16ix8, bool8ix16, bool32fx4,
+    bool32ix4, bool16ix8, bool8ix16,
     simd_eq, simd_ne, simd_lt, simd_le, simd_gt, simd_ge,
-    simd_shuffle2, simd_shuffle4, simd_shuffle8, simd_shuffle16,
+    simd_shuffle2, simd_shuffle4, simd_shuffle8, simd_shuffle16, simd_shuffle32,
     simd_insert, simd_extract,
     simd_cast,
     simd_add, simd_sub, simd_mul, simd_div, simd_shl, simd_shr, simd_and, simd_or, simd_xor,
-    bool8i, bool16i, bool32i, bool32f,
-    Unalign, bitcast,
+    bool8i, bool16i, bool32i,
+    Unalign, bitcast, TrueLanes, ParseVectorError, fmt_lanes, parse_lanes,
 };
 use super::sixty_four::*;
 #[cfg(all(target_feature = "avx"))]
@@ -34,10 +34,9 @@ pub struct f64x4(f64, f64, f64, f64);
 #[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Copy)]
 pub struct bool64ix4(i64, i64, i64, i64);
-#[repr(simd)]
-#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
-#[derive(Debug, Copy)]
-pub struct bool64fx4(i64, i64, i64, i64);
+/// A SIMD boolean vector for length-4 vectors of 64-bit floats.
+#[deprecated(note = "use `bool64ix4`, which works with `f64x4` too")]
+pub type bool64fx4 = bool64ix4;
 
 #[repr(simd)]
 #[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
@@ -58,11 +57,10 @@ pub struct f32x8(f32, f32, f32, f32,
 #[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Copy)]
 pub struct bool32ix8(i32, i32, i32, i32,
-                     i32, i32, i32, i32);#[repr(simd)]
-#[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
-#[derive(Debug, Copy)]
-pub struct bool32fx8(i32, i32, i32, i32,
                      i32, i32, i32, i32);
+/// A SIMD boolean vector for length-8 vectors of 32-bit floats.
+#[deprecated(note = "use `bool32ix8`, which works with `f32x8` too")]
+pub type bool32fx8 = bool32ix8;
 
 #[repr(simd)]
 #[cfg_attr(feature = "with-serde", derive(Serialize, Deserialize))]
@@ -105,21 +103,18 @@ pub struct bool8ix32(i8, i8, i8, i8, i8, i8, i8, i8,
 simd! {
     bool8ix32: i8x32 = i8, u8x32 = u8, bool8ix32 = bool8i;
     bool16ix16: i16x16 = i16, u16x16 = u16, bool16ix16 = bool16i;
-    bool32ix8: i32x8 = i32, u32x8 = u32, bool32ix8 = bool32i;
-    bool64ix4: i64x4 = i64, u64x4 = u64, bool64ix4 = bool64i;
-
-    bool32fx8: f32x8 = f32, bool32fx8 = bool32f;
-    bool64fx4: f64x4 = f64, bool64fx4 = bool64f;
+    bool32ix8: i32x8 = i32, u32x8 = u32, f32x8 = f32, bool32ix8 = bool32i;
+    bool64ix4: i64x4 = i64, u64x4 = u64, f64x4 = f64, bool64ix4 = bool64i;
 }
 
 basic_impls! {
     u64x4: u64, bool64ix4, simd_shuffle4, 4, x0, x1 | x2, x3;
     i64x4: i64, bool64ix4, simd_shuffle4, 4, x0, x1 | x2, x3;
-    f64x4: f64, bool64fx4, simd_shuffle4, 4, x0, x1 | x2, x3;
+    f64x4: f64, bool64ix4, simd_shuffle4, 4, x0, x1 | x2, x3;
 
     u32x8: u32, bool32ix8, simd_shuffle8, 8, x0, x1, x2, x3 | x4, x5, x6, x7;
     i32x8: i32, bool32ix8, simd_shuffle8, 8, x0, x1, x2, x3 | x4, x5, x6, x7;
-    f32x8: f32, bool32fx8, simd_shuffle8, 8, x0, x1, x2, x3 | x4, x5, x6, x7;
+    f32x8: f32, bool32ix8, simd_shuffle8, 8, x0, x1, x2, x3 | x4, x5, x6, x7;
 
     u16x16: u16, bool16ix16, simd_shuffle16, 16, x0, x1, x2, x3, x4, x5, x6, x7 | x8, x9, x10, x11, x12, x13, x14, x15;
     i16x16: i16, bool16ix16, simd_shuffle16, 16, x0, x1, x2, x3, x4, x5, x6, x7 | x8, x9, x10, x11, x12, x13, x14, x15;
@@ -129,11 +124,191 @@ basic_impls! {
     i8x32: i8, bool8ix32, simd_shuffle32, 32, x0, x1, x2, x3, x4, x5, x6, x7, x8, x9, x10, x11, x12, x13, x14, x15 |
         x16, x17, x18, x19, x20, x21, x22, x23, x24, x25, x26, x27, x28, x29, x30, x31;
 }
+int_trait_impls! {
+    u64x4: u64, 4;
+    i64x4: i64, 4;
+    u32x8: u32, 8;
+    i32x8: i32, 8;
+    u16x16: u16, 16;
+    i16x16: i16, 16;
+    u8x32: u8, 32;
+    i8x32: i8, 32;
+}
+byte_order_impls! {
+    u8x32: u8x32, 32;
+    i8x32: u8x32, 32;
+    u16x16: u16x16, 32;
+    i16x16: u16x16, 32;
+    u32x8: u32x8, 32;
+    i32x8: u32x8, 32;
+    f32x8: u32x8, 32;
+    u64x4: u64x4, 32;
+    i64x4: u64x4, 32;
+    f64x4: u64x4, 32;
+}
+bit_reversal_impls! {
+    u64x4: u8x32, u64x4_swap_bytes;
+    i64x4: u8x32, u64x4_swap_bytes;
+    u32x8: u8x32, u32x8_swap_bytes;
+    i32x8: u8x32, u32x8_swap_bytes;
+    u16x16: u8x32, u16x16_swap_bytes;
+    i16x16: u8x32, u16x16_swap_bytes;
+}
+impl u8x32 {
+    /// Reverse the order of the bits in each lane.
+    #[inline]
+    pub fn reverse_bits(self) -> Self {
+        common::u8x32_reverse_bits(self)
+    }
+}
+
+#[cfg(test)]
+mod bit_reversal_tests {
+    use super::*;
+
+    #[test]
+    fn u8x32_reverse_bits() {
+        check_bit_reversal!(reverse_bits: u8x32, u8x32, u8, 32);
+    }
+    #[test]
+    fn u16x16_bit_reversal() {
+        check_bit_reversal!(swap_bytes: u16x16, u16x16, u16, 16);
+        check_bit_reversal!(reverse_bits: u16x16, u16x16, u16, 16);
+    }
+    #[test]
+    fn i16x16_bit_reversal() {
+        check_bit_reversal!(swap_bytes: i16x16, u16x16, u16, 16);
+        check_bit_reversal!(reverse_bits: i16x16, u16x16, u16, 16);
+    }
+    #[test]
+    fn u32x8_bit_reversal() {
+        check_bit_reversal!(swap_bytes: u32x8, u32x8, u32, 8);
+        check_bit_reversal!(reverse_bits: u32x8, u32x8, u32, 8);
+    }
+    #[test]
+    fn i32x8_bit_reversal() {
+        check_bit_reversal!(swap_bytes: i32x8, u32x8, u32, 8);
+        check_bit_reversal!(reverse_bits: i32x8, u32x8, u32, 8);
+    }
+    #[test]
+    fn u64x4_bit_reversal() {
+        check_bit_reversal!(swap_bytes: u64x4, u64x4, u64, 4);
+        check_bit_reversal!(reverse_bits: u64x4, u64x4, u64, 4);
+    }
+    #[test]
+    fn i64x4_bit_reversal() {
+        check_bit_reversal!(swap_bytes: i64x4, u64x4, u64, 4);
+        check_bit_reversal!(reverse_bits: i64x4, u64x4, u64, 4);
+    }
+}
+
+#[cfg(test)]
+mod byte_order_tests {
+    use super::*;
+
+    #[test]
+    fn u8x32_byte_order() {
+        check_byte_order!(u8x32, u8x32, 32, 1);
+    }
+    #[test]
+    fn i8x32_byte_order() {
+        check_byte_order!(i8x32, u8x32, 32, 1);
+    }
+    #[test]
+    fn u16x16_byte_order() {
+        check_byte_order!(u16x16, u16x16, 32, 2);
+    }
+    #[test]
+    fn i16x16_byte_order() {
+        check_byte_order!(i16x16, u16x16, 32, 2);
+    }
+    #[test]
+    fn u32x8_byte_order() {
+        check_byte_order!(u32x8, u32x8, 32, 4);
+    }
+    #[test]
+    fn i32x8_byte_order() {
+        check_byte_order!(i32x8, u32x8, 32, 4);
+    }
+    #[test]
+    fn f32x8_byte_order() {
+        check_byte_order!(f32x8, u32x8, 32, 4);
+    }
+    #[test]
+    fn u64x4_byte_order() {
+        check_byte_order!(u64x4, u64x4, 32, 8);
+    }
+    #[test]
+    fn i64x4_byte_order() {
+        check_byte_order!(i64x4, u64x4, 32, 8);
+    }
+    #[test]
+    fn f64x4_byte_order() {
+        check_byte_order!(f64x4, u64x4, 32, 8);
+    }
+}
+
+#[cfg(test)]
+mod format_tests {
+    use super::*;
+
+    #[test]
+    fn u16x16_display() {
+        check_display!(u16x16, u16, 16, [0, 1, 65535, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14,
+                                         15, 16]);
+    }
+    #[test]
+    fn f32x8_display() {
+        check_display!(f32x8, f32, 8, [0.5, -0.0, 1e-10, 6.0, 7.5, 8.25, 9.125, -1e10,
+                                       ::std::f32::NAN]);
+    }
+}
+
+#[cfg(test)]
+mod masked_op_tests {
+    use super::*;
+
+    #[test]
+    fn f64x4_masked_ops() {
+        check_masked_ops!(f64x4, f64, bool64ix4, 4);
+    }
+    #[test]
+    fn f32x8_masked_ops() {
+        check_masked_ops!(f32x8, f32, bool32ix8, 8);
+    }
+    #[test]
+    fn u16x16_masked_ops() {
+        check_masked_ops!(u16x16, u16, bool16ix16, 16);
+    }
+    #[test]
+    fn i8x32_masked_ops() {
+        check_masked_ops!(i8x32, i8, bool8ix32, 32);
+    }
+}
+
+#[cfg(test)]
+mod iter_trait_tests {
+    use super::*;
+
+    #[test]
+    fn f64x4_iter_traits() {
+        check_iter_traits!(f64x4, f64, 4);
+    }
+    #[test]
+    fn u16x16_iter_traits() {
+        check_iter_traits!(u16x16, u16, 16);
+    }
+    #[test]
+    fn i8x32_iter_traits() {
+        check_iter_traits!(i8x32, i8, 32);
+    }
+}
 
 #[cfg(all(not(target_feature = "avx")))]
 #[doc(hidden)]
 mod common {
     use super::*;
+    use super::{simd_shuffle4, simd_shuffle8, simd_shuffle16, simd_shuffle32};
     // implementation via SSE vectors
     macro_rules! bools {
         ($($ty: ty, $all: ident, $any: ident;)*) => {
@@ -152,40 +327,134 @@ mod common {
 
     bools! {
         bool64ix4, bool64ix4_all, bool64ix4_any;
-        bool64fx4, bool64fx4_all, bool64fx4_any;
         bool32ix8, bool32ix8_all, bool32ix8_any;
-        bool32fx8, bool32fx8_all, bool32fx8_any;
         bool16ix16, bool16ix16_all, bool16ix16_any;
         bool8ix32, bool8ix32_all, bool8ix32_any;
     }
 
+    macro_rules! min_max_nums {
+        ($($ty: ident: $min_num: ident, $max_num: ident, $shuffle: ident $idx: tt;)*) => {
+            $(
+                #[inline]
+                pub fn $min_num(x: $ty, y: $ty) -> $ty {
+                    unsafe {$shuffle(x.low().min_num(y.low()), x.high().min_num(y.high()), $idx)}
+                }
+                #[inline]
+                pub fn $max_num(x: $ty, y: $ty) -> $ty {
+                    unsafe {$shuffle(x.low().max_num(y.low()), x.high().max_num(y.high()), $idx)}
+                }
+                )*
+        }
+    }
+
+    macro_rules! bitmasks {
+        ($($ty: ty, $bitmask: ident, $half_length: expr;)*) => {
+            $(
+                #[inline]
+                pub fn $bitmask(x: $ty) -> u64 {
+                    x.low().to_bitmask() | x.high().to_bitmask() << $half_length
+                }
+                )*
+        }
+    }
+
+    min_max_nums! {
+        f64x4: f64x4_min_num, f64x4_max_num, simd_shuffle4 [0, 1, 2, 3];
+        f32x8: f32x8_min_num, f32x8_max_num, simd_shuffle8 [0, 1, 2, 3, 4, 5, 6, 7];
+    }
+
+    bitmasks! {
+        bool64ix4, bool64ix4_bitmask, 2;
+        bool32ix8, bool32ix8_bitmask, 4;
+        bool16ix16, bool16ix16_bitmask, 8;
+        bool8ix32, bool8ix32_bitmask, 16;
+    }
+
+    bitwise_selects! {
+        bool64ix4: bool64ix4_select(i64x4);
+        bool32ix8: bool32ix8_select(i32x8);
+        bool16ix16: bool16ix16_select(i16x16);
+        bool8ix32: bool8ix32_select(i8x32);
+    }
+
+    half_bit_swaps! {
+        u64x4_swap_bytes(u64x4): swap_bytes, simd_shuffle4 [0, 1, 2, 3];
+        u32x8_swap_bytes(u32x8): swap_bytes, simd_shuffle8 [0, 1, 2, 3, 4, 5, 6, 7];
+        u16x16_swap_bytes(u16x16): swap_bytes, simd_shuffle16 [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+        u8x32_reverse_bits(u8x32): reverse_bits, simd_shuffle32 [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
+                                                                 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31];
+    }
 }
 
 bool_impls! {
-    bool64ix4: bool64i, i64x4, i64, 4, bool64ix4_all, bool64ix4_any, x0, x1 | x2, x3
+    bool64ix4: bool64i, i64x4, i64, 4, bool64ix4_all, bool64ix4_any, bool64ix4_bitmask, bool64ix4_select, x0, x1 | x2, x3
         [/// Convert `self` to a boolean vector for interacting with floating point vectors.
-         to_f -> bool64fx4];
-
-    bool64fx4: bool64f, i64x4, i64, 4, bool64fx4_all, bool64fx4_any, x0, x1 | x2, x3
-        [/// Convert `self` to a boolean vector for interacting with integer vectors.
+         #[deprecated(note = "`bool64ix4` works with floating point vectors directly")]
+         to_f -> bool64ix4,
+         /// Convert `self` to a boolean vector for interacting with integer vectors.
+         #[deprecated(note = "`bool64ix4` works with integer vectors directly")]
          to_i -> bool64ix4];
 
-    bool32ix8: bool32i, i32x8, i32, 8, bool32ix8_all, bool32ix8_any, x0, x1, x2, x3 | x4, x5, x6, x7
+    bool32ix8: bool32i, i32x8, i32, 8, bool32ix8_all, bool32ix8_any, bool32ix8_bitmask, bool32ix8_select, x0, x1, x2, x3 | x4, x5, x6, x7
         [/// Convert `self` to a boolean vector for interacting with floating point vectors.
-         to_f -> bool32fx8];
-
-    bool32fx8: bool32f, i32x8, i32, 8, bool32fx8_all, bool32fx8_any, x0, x1, x2, x3 | x4, x5, x6, x7
-        [/// Convert `self` to a boolean vector for interacting with integer vectors.
+         #[deprecated(note = "`bool32ix8` works with floating point vectors directly")]
+         to_f -> bool32ix8,
+         /// Convert `self` to a boolean vector for interacting with integer vectors.
+         #[deprecated(note = "`bool32ix8` works with integer vectors directly")]
          to_i -> bool32ix8];
 
-    bool16ix16: bool16i, i16x16, i16, 16, bool16ix16_all, bool16ix16_any,
+    bool16ix16: bool16i, i16x16, i16, 16, bool16ix16_all, bool16ix16_any, bool16ix16_bitmask, bool16ix16_select,
             x0, x1, x2, x3, x4, x5, x6, x7 | x8, x9, x10, x11, x12, x13, x14, x15 [];
 
-    bool8ix32: bool8i, i8x32, i8, 32, bool8ix32_all, bool8ix32_any,
+    bool8ix32: bool8i, i8x32, i8, 32, bool8ix32_all, bool8ix32_any, bool8ix32_bitmask, bool8ix32_select,
         x0, x1, x2, x3, x4, x5, x6, x7, x8, x9, x10, x11, x12, x13, x14, x15 |
         x16, x17, x18, x19, x20, x21, x22, x23, x24, x25, x26, x27, x28, x29, x30, x31 [];
 }
 
+#[cfg(test)]
+mod bitmask_tests {
+    use super::*;
+
+    #[test]
+    fn bool64ix4_bitmask() {
+        check_bitmask!(bool64ix4, 4);
+    }
+    #[test]
+    fn bool32ix8_bitmask() {
+        check_bitmask!(bool32ix8, 8);
+    }
+    #[test]
+    fn bool16ix16_bitmask() {
+        check_bitmask!(bool16ix16, 16);
+    }
+    #[test]
+    fn bool8ix32_bitmask() {
+        check_bitmask!(bool8ix32, 32);
+    }
+}
+
+#[cfg(test)]
+mod lane_query_tests {
+    use super::*;
+
+    #[test]
+    fn bool64ix4_lane_queries() {
+        check_lane_queries!(bool64ix4, 4);
+    }
+    #[test]
+    fn bool32ix8_lane_queries() {
+        check_lane_queries!(bool32ix8, 8);
+    }
+    #[test]
+    fn bool16ix16_lane_queries() {
+        check_lane_queries!(bool16ix16, 16);
+    }
+    #[test]
+    fn bool8ix32_lane_queries() {
+        check_lane_queries!(bool8ix32, 32);
+    }
+}
+
 pub trait LowHigh128 {
     type Half: Simd;
     /// Extract the low 128 bit part.
@@ -255,9 +524,6 @@ macro_rules! bool_low_high_impls {
 }
 
 bool_low_high_impls! {
-    bool64fx4: bool64fx2;
-    bool32fx8: bool32fx4;
-
     bool64ix4: bool64ix2;
     bool32ix8: bool32ix4;
     bool16ix16: bool16ix8;
@@ -292,14 +558,30 @@ impl i64x4 {
 
 impl f64x4 {
     /// Convert each lane to a signed integer.
+    ///
+    /// NaN and out of range lanes give platform dependent results,
+    /// unless the `deterministic` feature is enabled, when this is
+    /// the same as `to_i64_saturating`.
     #[inline]
     pub fn to_i64(self) -> i64x4 {
-        unsafe {simd_cast(self)}
+        if cfg!(feature = "deterministic") {
+            self.to_i64_saturating()
+        } else {
+            unsafe {simd_cast(self)}
+        }
     }
     /// Convert each lane to an unsigned integer.
+    ///
+    /// NaN and out of range lanes give platform dependent results,
+    /// unless the `deterministic` feature is enabled, when this is
+    /// the same as `to_u64_saturating`.
     #[inline]
     pub fn to_u64(self) -> u64x4 {
-        unsafe {simd_cast(self)}
+        if cfg!(feature = "deterministic") {
+            self.to_u64_saturating()
+        } else {
+            unsafe {simd_cast(self)}
+        }
     }
 }
 
@@ -331,14 +613,30 @@ impl i32x8 {
 
 impl f32x8 {
     /// Convert each lane to a signed integer.
+    ///
+    /// NaN and out of range lanes give platform dependent results,
+    /// unless the `deterministic` feature is enabled, when this is
+    /// the same as `to_i32_saturating`.
     #[inline]
     pub fn to_i32(self) -> i32x8 {
-        unsafe {simd_cast(self)}
+        if cfg!(feature = "deterministic") {
+            self.to_i32_saturating()
+        } else {
+            unsafe {simd_cast(self)}
+        }
     }
     /// Convert each lane to an unsigned integer.
+    ///
+    /// NaN and out of range lanes give platform dependent results,
+    /// unless the `deterministic` feature is enabled, when this is
+    /// the same as `to_u32_saturating`.
     #[inline]
     pub fn to_u32(self) -> u32x8 {
-        unsafe {simd_cast(self)}
+        if cfg!(feature = "deterministic") {
+            self.to_u32_saturating()
+        } else {
+            unsafe {simd_cast(self)}
+        }
     }
 }
 
@@ -388,16 +686,176 @@ operators! {
 
     BitAnd (simd_and, bitand):
         i8x32, u8x32, i16x16, u16x16, i32x8, u32x8, i64x4, u64x4,
-        bool64ix4, bool32ix8, bool16ix16,
-        bool64fx4, bool32fx8;
+        bool64ix4, bool32ix8, bool16ix16, bool8ix32;
     BitOr (simd_or, bitor):
         i8x32, u8x32, i16x16, u16x16, i32x8, u32x8, i64x4, u64x4,
-        bool64ix4, bool32ix8, bool16ix16,
-        bool64fx4, bool32fx8;
+        bool64ix4, bool32ix8, bool16ix16, bool8ix32;
     BitXor (simd_xor, bitxor):
         i8x32, u8x32, i16x16, u16x16, i32x8, u32x8, i64x4, u64x4,
-        bool64ix4, bool32ix8, bool16ix16,
-        bool64fx4, bool32fx8;
+        bool64ix4, bool32ix8, bool16ix16, bool8ix32;
+}
+
+float_to_int! {
+    f32x8 (i32x8, 8388608.0) {
+        i32x8, i32, bool32ix8:
+            to_i32_saturating, to_i32_round, to_i32_floor, try_to_i32,
+            ge -2147483648.0, 2147483648.0;
+        u32x8, u32, bool32ix8:
+            to_u32_saturating, to_u32_round, to_u32_floor, try_to_u32,
+            gt -1.0, 4294967296.0;
+    }
+    f64x4 (i64x4, 4503599627370496.0) {
+        i64x4, i64, bool64ix4:
+            to_i64_saturating, to_i64_round, to_i64_floor, try_to_i64,
+            ge -9223372036854775808.0, 9223372036854775808.0;
+        u64x4, u64, bool64ix4:
+            to_u64_saturating, to_u64_round, to_u64_floor, try_to_u64,
+            gt -1.0, 18446744073709551616.0;
+    }
+}
+
+#[cfg(test)]
+mod float_to_int_tests {
+    use super::*;
+
+    #[test]
+    fn f32x8_to_i32() {
+        check_float_to_int!(f32x8, f32, i32, 8,
+                            to_i32_saturating, to_i32_round, to_i32_floor, try_to_i32);
+    }
+    #[test]
+    fn f32x8_to_u32() {
+        check_float_to_int!(f32x8, f32, u32, 8,
+                            to_u32_saturating, to_u32_round, to_u32_floor, try_to_u32);
+    }
+    #[test]
+    fn f64x4_to_i64() {
+        check_float_to_int!(f64x4, f64, i64, 4,
+                            to_i64_saturating, to_i64_round, to_i64_floor, try_to_i64);
+    }
+    #[test]
+    fn f64x4_to_u64() {
+        check_float_to_int!(f64x4, f64, u64, 4,
+                            to_u64_saturating, to_u64_round, to_u64_floor, try_to_u64);
+    }
+}
+widening_mul_impls! {
+    u64x4: split u64x4, false;
+    i64x4: split u64x4, true;
+}
+widening_mul_impls! {
+    u32x8: halves simd_shuffle8 [0, 1, 2, 3, 4, 5, 6, 7];
+    i32x8: halves simd_shuffle8 [0, 1, 2, 3, 4, 5, 6, 7];
+    u16x16: halves simd_shuffle16 [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+    i16x16: halves simd_shuffle16 [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+    u8x32: halves simd_shuffle32 [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
+                                  16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31];
+    i8x32: halves simd_shuffle32 [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
+                                  16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31];
+}
+
+#[cfg(test)]
+mod widening_mul_tests {
+    use super::*;
+
+    #[test]
+    fn u64x4_widening_mul() {
+        check_widening_mul_64!(u64x4, u64, mul_u64, 4);
+    }
+    #[test]
+    fn i64x4_widening_mul() {
+        check_widening_mul_64!(i64x4, i64, mul_i64, 4);
+    }
+    #[test]
+    fn u32x8_widening_mul() {
+        check_widening_mul!(u32x8, u32, u64, 8);
+    }
+    #[test]
+    fn i32x8_widening_mul() {
+        check_widening_mul!(i32x8, i32, i64, 8);
+    }
+    #[test]
+    fn u16x16_widening_mul() {
+        check_widening_mul!(u16x16, u16, u32, 16);
+    }
+    #[test]
+    fn i16x16_widening_mul() {
+        check_widening_mul!(i16x16, i16, i32, 16);
+    }
+    #[test]
+    fn u8x32_widening_mul() {
+        check_widening_mul!(u8x32, u8, u16, 32);
+    }
+    #[test]
+    fn i8x32_widening_mul() {
+        check_widening_mul!(i8x32, i8, i16, 32);
+    }
+}
+
+int_div_impls! {
+    i64x4, 4;
+    u64x4, 4;
+    i32x8, 8;
+    u32x8, 8;
+    i16x16, 16;
+    u16x16, 16;
+    i8x32, 32;
+    u8x32, 32;
+}
+operator_forms! {
+    Add, AddAssign (add, add_assign):
+        i8x32: i8, u8x32: u8, i16x16: i16, u16x16: u16, i32x8: i32, u32x8: u32, i64x4: i64, u64x4: u64, f32x8: f32, f64x4: f64;
+    Sub, SubAssign (sub, sub_assign):
+        i8x32: i8, u8x32: u8, i16x16: i16, u16x16: u16, i32x8: i32, u32x8: u32, i64x4: i64, u64x4: u64, f32x8: f32, f64x4: f64;
+    Mul, MulAssign (mul, mul_assign):
+        i8x32: i8, u8x32: u8, i16x16: i16, u16x16: u16, i32x8: i32, u32x8: u32, i64x4: i64, u64x4: u64, f32x8: f32, f64x4: f64;
+    Div, DivAssign (div, div_assign):
+        i8x32: i8, u8x32: u8, i16x16: i16, u16x16: u16, i32x8: i32, u32x8: u32, i64x4: i64, u64x4: u64, f32x8: f32, f64x4: f64;
+    Rem, RemAssign (rem, rem_assign):
+        i8x32: i8, u8x32: u8, i16x16: i16, u16x16: u16, i32x8: i32, u32x8: u32, i64x4: i64, u64x4: u64;
+
+    BitAnd, BitAndAssign (bitand, bitand_assign):
+        i8x32: i8, u8x32: u8, i16x16: i16, u16x16: u16, i32x8: i32, u32x8: u32, i64x4: i64, u64x4: u64,
+        f32x8: f32, f64x4: f64,
+        bool8ix32: bool, bool16ix16: bool, bool32ix8: bool, bool64ix4: bool;
+    BitOr, BitOrAssign (bitor, bitor_assign):
+        i8x32: i8, u8x32: u8, i16x16: i16, u16x16: u16, i32x8: i32, u32x8: u32, i64x4: i64, u64x4: u64,
+        f32x8: f32, f64x4: f64,
+        bool8ix32: bool, bool16ix16: bool, bool32ix8: bool, bool64ix4: bool;
+    BitXor, BitXorAssign (bitxor, bitxor_assign):
+        i8x32: i8, u8x32: u8, i16x16: i16, u16x16: u16, i32x8: i32, u32x8: u32, i64x4: i64, u64x4: u64,
+        f32x8: f32, f64x4: f64,
+        bool8ix32: bool, bool16ix16: bool, bool32ix8: bool, bool64ix4: bool;
+}
+
+#[cfg(test)]
+mod operator_form_tests {
+    use super::*;
+
+    #[test]
+    fn f64x4_operator_forms() {
+        check_operator_forms!(f64x4, f64, 4, 7.5, -2.0;
+                              + +=, - -=, * *=, / /=, & &=, | |=, ^ ^=);
+    }
+    #[test]
+    fn f32x8_operator_forms() {
+        check_operator_forms!(f32x8, f32, 8, 7.5, -2.0;
+                              + +=, - -=, * *=, / /=, & &=, | |=, ^ ^=);
+    }
+    #[test]
+    fn u16x16_operator_forms() {
+        check_operator_forms!(u16x16, u16, 16, 1000, 7;
+                              + +=, - -=, * *=, / /=, % %=, & &=, | |=, ^ ^=);
+    }
+    #[test]
+    fn i8x32_operator_forms() {
+        check_operator_forms!(i8x32, i8, 32, -7, 3;
+                              + +=, - -=, * *=, / /=, % %=, & &=, | |=, ^ ^=);
+    }
+    #[test]
+    fn bool8ix32_operator_forms() {
+        check_operator_forms!(bool8ix32, bool, 32, true, false; & &=, | |=, ^ ^=);
+    }
 }
 
 neg_impls!{
@@ -414,6 +872,160 @@ neg_impls! {
     f32x8,
 }
 
+fma_impls! {
+    f64x4: f64x4_mul_add, f64x4_mul_sub, f64x4_neg_mul_add;
+    f32x8: f32x8_mul_add, f32x8_mul_sub, f32x8_neg_mul_add;
+}
+
+#[cfg(test)]
+mod fma_tests {
+    use super::*;
+
+    #[test]
+    fn f64x4_fma() {
+        check_fma!(f64x4, f64, 4);
+    }
+    #[test]
+    fn f32x8_fma() {
+        check_fma!(f32x8, f32, 8);
+    }
+}
+
+float_bit_impls! {
+    f64x4: u64x4;
+    f32x8: u32x8;
+}
+
+#[cfg(test)]
+mod float_bit_tests {
+    use super::*;
+
+    #[test]
+    fn f64x4_float_bits() {
+        check_float_bits!(f64x4, f64, u64, 4);
+    }
+    #[test]
+    fn f32x8_float_bits() {
+        check_float_bits!(f32x8, f32, u32, 8);
+    }
+}
+
+float_class_impls! {
+    f64x4: f64, bool64ix4;
+    f32x8: f32, bool32ix8;
+}
+
+#[cfg(test)]
+mod float_class_tests {
+    use super::*;
+
+    #[test]
+    fn f64x4_float_class() {
+        check_float_class!(f64x4, f64, 4);
+    }
+    #[test]
+    fn f32x8_float_class() {
+        check_float_class!(f32x8, f32, 8);
+    }
+}
+
+min_max_impls! {
+    f64x4: f64x4_min_num, f64x4_max_num;
+    f32x8: f32x8_min_num, f32x8_max_num;
+}
+
+#[cfg(test)]
+mod min_max_tests {
+    use super::*;
+
+    #[test]
+    fn f64x4_min_max() {
+        check_min_max!(f64x4, f64, 4);
+    }
+    #[test]
+    fn f32x8_min_max() {
+        check_min_max!(f32x8, f32, 8);
+    }
+}
+
+f64_approx_impls! {
+    f64x4: u64x4;
+}
+refine_impls! {
+    f64x4,
+}
+
+// `approx_rsqrt` and `approx_reciprocal` for `f32x8` need AVX (see
+// `AvxF32x8`), so these refine each half.
+macro_rules! refine_halves {
+    ($($name: ident,)*) => {
+        impl f32x8 {
+            $(
+                /// Like the `f32x4` method of the same name, on each
+                /// half.
+                #[inline]
+                pub fn $name(self) -> Self {
+                    let lo = self.low().$name();
+                    let hi = self.high().$name();
+                    unsafe {simd_shuffle8(lo, hi, [0, 1, 2, 3, 4, 5, 6, 7])}
+                }
+            )*
+        }
+    }
+}
+refine_halves! {
+    rsqrt_precise1,
+    rsqrt_precise2,
+    recip_precise1,
+    recip_precise2,
+}
+
+#[cfg(test)]
+mod refine_tests {
+    use super::*;
+
+    #[test]
+    fn f64x4_rsqrt_precise1() {
+        check_refine!(f64x4, f64, 4, rsqrt_precise1, -1074..1024,
+                      17, 1, |b| 2 * b - 1, 51, |x: f64| 1.0 / x.sqrt());
+    }
+    #[test]
+    fn f64x4_rsqrt_precise2() {
+        check_refine!(f64x4, f64, 4, rsqrt_precise2, -1074..1024,
+                      17, 2, |b| 2 * b - 1, 51, |x: f64| 1.0 / x.sqrt());
+    }
+    #[test]
+    fn f64x4_recip_precise1() {
+        check_refine!(f64x4, f64, 4, recip_precise1, -1022..1022,
+                      17, 1, |b| 2 * b, 51, |x: f64| 1.0 / x);
+    }
+    #[test]
+    fn f64x4_recip_precise2() {
+        check_refine!(f64x4, f64, 4, recip_precise2, -1022..1022,
+                      17, 2, |b| 2 * b, 51, |x: f64| 1.0 / x);
+    }
+    #[test]
+    fn f32x8_rsqrt_precise1() {
+        check_refine!(f32x8, f32, 8, rsqrt_precise1, -126..128,
+                      ::common::F32X4_APPROX_BITS, 1, |b| 2 * b - 1, 22, |x: f64| 1.0 / x.sqrt());
+    }
+    #[test]
+    fn f32x8_rsqrt_precise2() {
+        check_refine!(f32x8, f32, 8, rsqrt_precise2, -126..128,
+                      ::common::F32X4_APPROX_BITS, 2, |b| 2 * b - 1, 22, |x: f64| 1.0 / x.sqrt());
+    }
+    #[test]
+    fn f32x8_recip_precise1() {
+        check_refine!(f32x8, f32, 8, recip_precise1, -126..126,
+                      ::common::F32X4_APPROX_BITS, 1, |b| 2 * b, 22, |x: f64| 1.0 / x);
+    }
+    #[test]
+    fn f32x8_recip_precise2() {
+        check_refine!(f32x8, f32, 8, recip_precise2, -126..126,
+                      ::common::F32X4_APPROX_BITS, 2, |b| 2 * b, 22, |x: f64| 1.0 / x);
+    }
+}
+
 not_impls! {
     i64x4,
     u64x4,
@@ -435,3 +1047,475 @@ shift! {
     i8x32,
     u8x32
 }
+
+vector_shifts! {
+    i64x4 (u64x4, 64): i64x4, u64x4;
+    u64x4 (u64x4, 64): u64x4;
+    i32x8 (u32x8, 32): i32x8, u32x8;
+    u32x8 (u32x8, 32): u32x8;
+    i16x16 (u16x16, 16): i16x16, u16x16;
+    u16x16 (u16x16, 16): u16x16;
+    i8x32 (u8x32, 8): i8x32, u8x32;
+    u8x32 (u8x32, 8): u8x32;
+}
+
+#[cfg(test)]
+mod shift_tests {
+    use super::*;
+
+    #[test]
+    fn i64x4_shifts() {
+        check_shifts!(i64x4, i64, u64x4, u64, 4);
+    }
+    #[test]
+    fn u64x4_shifts() {
+        check_shifts!(u64x4, u64, u64x4, u64, 4);
+    }
+    #[test]
+    fn i32x8_shifts() {
+        check_shifts!(i32x8, i32, u32x8, u32, 8);
+    }
+    #[test]
+    fn u32x8_shifts() {
+        check_shifts!(u32x8, u32, u32x8, u32, 8);
+    }
+    #[test]
+    fn i16x16_shifts() {
+        check_shifts!(i16x16, i16, u16x16, u16, 16);
+    }
+    #[test]
+    fn u16x16_shifts() {
+        check_shifts!(u16x16, u16, u16x16, u16, 16);
+    }
+    #[test]
+    fn i8x32_shifts() {
+        check_shifts!(i8x32, i8, u8x32, u8, 32);
+    }
+    #[test]
+    fn u8x32_shifts() {
+        check_shifts!(u8x32, u8, u8x32, u8, 32);
+    }
+}
+
+macro_rules! conversions {
+    ($($narrow: ident <-> $wide: ident = 2 x $half: ident: $saturate: ident;)*) => {
+        $(impl $narrow {
+            /// Convert each lane to a lane twice as wide, without
+            /// loss.
+            #[inline]
+            pub fn widen(self) -> $wide {
+                unsafe {simd_cast(self)}
+            }
+            /// Convert each lane to a lane twice as wide, without
+            /// loss, returning the low and the high halves of the
+            /// result.
+            #[inline]
+            pub fn widen_halves(self) -> ($half, $half) {
+                let x = self.widen();
+                (x.low(), x.high())
+            }
+        }
+          impl $wide {
+              /// Convert each lane to a lane half as wide, keeping
+              /// only the low bits.
+              #[inline]
+              pub fn narrow_truncate(self) -> $narrow {
+                  unsafe {simd_cast(self)}
+              }
+              /// Convert each lane to a lane half as wide, clamping
+              /// values outside the range of the narrower lane to
+              /// its minimum and maximum.
+              #[inline]
+              pub fn narrow_saturate(self) -> $narrow {
+                  saturate::$saturate(self)
+              }
+          })*
+    }
+}
+conversions! {
+    u8x16 <-> u16x16 = 2 x u16x8: u16x16_to_u8x16;
+    i8x16 <-> i16x16 = 2 x i16x8: i16x16_to_i8x16;
+    u16x8 <-> u32x8 = 2 x u32x4: u32x8_to_u16x8;
+    i16x8 <-> i32x8 = 2 x i32x4: i32x8_to_i16x8;
+    u32x4 <-> u64x4 = 2 x u64x2: u64x4_to_u32x4;
+    i32x4 <-> i64x4 = 2 x i64x2: i64x4_to_i32x4;
+}
+
+impl f32x4 {
+    /// Convert each lane to a 64-bit float, without loss.
+    #[inline]
+    pub fn widen(self) -> f64x4 {
+        unsafe {simd_cast(self)}
+    }
+    /// Convert each lane to a 64-bit float, without loss, returning
+    /// the low and the high halves of the result.
+    #[inline]
+    pub fn widen_halves(self) -> (f64x2, f64x2) {
+        let x = self.widen();
+        (x.low(), x.high())
+    }
+}
+impl f64x4 {
+    /// Convert each lane to a 32-bit float, rounding to nearest.
+    ///
+    /// Lanes outside the range of `f32` become infinite, like `as
+    /// f32`.
+    #[inline]
+    pub fn narrow(self) -> f32x4 {
+        unsafe {simd_cast(self)}
+    }
+}
+
+macro_rules! bool_conversions {
+    ($($narrow: ident <-> $wide: ident = 2 x $half: ident;)*) => {
+        $(impl $narrow {
+            /// Convert each lane to a lane twice as wide.
+            #[inline]
+            pub fn widen(self) -> $wide {
+                $wide::from_repr(unsafe {simd_cast(self.to_repr())})
+            }
+            /// Convert each lane to a lane twice as wide, returning
+            /// the low and the high halves of the result.
+            #[inline]
+            pub fn widen_halves(self) -> ($half, $half) {
+                let x = self.widen();
+                (x.low(), x.high())
+            }
+        }
+          impl $wide {
+              /// Convert each lane to a lane half as wide.
+              #[inline]
+              pub fn narrow(self) -> $narrow {
+                  $narrow::from_repr(unsafe {simd_cast(self.to_repr())})
+              }
+          })*
+    }
+}
+bool_conversions! {
+    bool8ix16 <-> bool16ix16 = 2 x bool16ix8;
+    bool16ix8 <-> bool32ix8 = 2 x bool32ix4;
+    bool32ix4 <-> bool64ix4 = 2 x bool64ix2;
+}
+
+macro_rules! bool_widen_halves {
+    ($($name: ident -> 2 x $half: ident;)*) => {
+        $(impl $name {
+            /// Convert each lane to a lane twice as wide, returning
+            /// the low and the high halves of the result.
+            #[inline]
+            pub fn widen_halves(self) -> ($half, $half) {
+                (self.low().widen(), self.high().widen())
+            }
+        })*
+    }
+}
+bool_widen_halves! {
+    bool8ix32 -> 2 x bool16ix16;
+    bool16ix16 -> 2 x bool32ix8;
+    bool32ix8 -> 2 x bool64ix4;
+}
+
+// Every lane of a boolean vector is all ones or all zeros, so taking
+// either half of each lane narrows it.
+macro_rules! bool_packs {
+    ($($name: ident -> $narrow: ident ($repr: ident, $shuffle: ident [$($idx: expr),*]);)*) => {
+        $(impl $name {
+            /// Convert each lane of `self` and `high` to a lane half
+            /// as wide, and join them into one vector, with the
+            /// lanes of `self` first.
+            #[inline]
+            pub fn pack(self, high: Self) -> $narrow {
+                let low: $repr = bitcast(self);
+                let high: $repr = bitcast(high);
+                $narrow::from_repr(unsafe {$shuffle(low, high, [$($idx),*])})
+            }
+        })*
+    }
+}
+bool_packs! {
+    bool64ix2 -> bool32ix4 (i32x4, simd_shuffle4 [0, 2, 4, 6]);
+    bool32ix4 -> bool16ix8 (i16x8, simd_shuffle8 [0, 2, 4, 6, 8, 10, 12, 14]);
+    bool16ix8 -> bool8ix16 (i8x16, simd_shuffle16 [0, 2, 4, 6, 8, 10, 12, 14,
+                                                    16, 18, 20, 22, 24, 26, 28, 30]);
+
+    bool64ix4 -> bool32ix8 (i32x8, simd_shuffle8 [0, 2, 4, 6, 8, 10, 12, 14]);
+    bool32ix8 -> bool16ix16 (i16x16, simd_shuffle16 [0, 2, 4, 6, 8, 10, 12, 14,
+                                                      16, 18, 20, 22, 24, 26, 28, 30]);
+    bool16ix16 -> bool8ix32 (i8x32, simd_shuffle32 [0, 2, 4, 6, 8, 10, 12, 14,
+                                                    16, 18, 20, 22, 24, 26, 28, 30,
+                                                    32, 34, 36, 38, 40, 42, 44, 46,
+                                                    48, 50, 52, 54, 56, 58, 60, 62]);
+}
+
+#[cfg(test)]
+mod bool_conversion_tests {
+    use super::*;
+
+    const MASKS: [u64; 5] = [0, !0, 0x5555_5555_5555_5555, 0xdead_beef_0123_4567,
+                             0x8000_0000_0000_0001];
+
+    // Every lane must stay all ones or all zeros.
+    macro_rules! check_repr {
+        ($x: expr, $length: expr) => {{
+            let r = $x.to_repr();
+            for i in 0..$length {
+                assert!(r.extract(i) == 0 || r.extract(i) == -1, "{:?}", r);
+            }
+        }}
+    }
+
+    macro_rules! check_widen {
+        ($($ty: ident: $half_length: expr;)*) => {$(
+            for &bits in &MASKS {
+                let x = $ty::from_bitmask(bits);
+                let (low, high) = x.widen_halves();
+                check_repr!(low, $half_length);
+                check_repr!(high, $half_length);
+                assert_eq!(low.to_bitmask() | high.to_bitmask() << $half_length,
+                           x.to_bitmask());
+            }
+        )*}
+    }
+    macro_rules! check_narrow {
+        ($($ty: ident: $length: expr;)*) => {$(
+            for &bits in &MASKS {
+                let x = $ty::from_bitmask(bits);
+                let wide = x.widen();
+                check_repr!(wide, $length);
+                assert_eq!(wide.to_bitmask(), x.to_bitmask());
+                let narrow = wide.narrow();
+                check_repr!(narrow, $length);
+                assert_eq!(narrow.to_bitmask(), x.to_bitmask());
+            }
+        )*}
+    }
+    macro_rules! check_pack {
+        ($($ty: ident: $length: expr;)*) => {$(
+            for &bits in &MASKS {
+                let (low, high) = ($ty::from_bitmask(bits), $ty::from_bitmask(bits.rotate_left(17)));
+                let x = low.pack(high);
+                check_repr!(x, 2 * $length);
+                assert_eq!(x.to_bitmask(), low.to_bitmask() | high.to_bitmask() << $length);
+            }
+        )*}
+    }
+
+    #[test]
+    fn widen_halves() {
+        check_widen! {
+            bool8ix16: 8;
+            bool16ix8: 4;
+            bool32ix4: 2;
+            bool8ix32: 16;
+            bool16ix16: 8;
+            bool32ix8: 4;
+        }
+    }
+    #[test]
+    fn widen_narrow() {
+        check_narrow! {
+            bool8ix16: 16;
+            bool16ix8: 8;
+            bool32ix4: 4;
+        }
+    }
+    #[test]
+    fn pack() {
+        check_pack! {
+            bool64ix2: 2;
+            bool32ix4: 4;
+            bool16ix8: 8;
+            bool64ix4: 4;
+            bool32ix8: 8;
+            bool16ix16: 16;
+        }
+    }
+}
+
+mod saturate {
+    use super::*;
+    #[cfg(all(any(target_arch = "x86",
+                  target_arch = "x86_64"),
+              target_feature = "sse2"))]
+    use x86::sse2::{Sse2I32x4, Sse2I16x8};
+
+    macro_rules! clamps {
+        ($($name: ident: $wide: ident, $welem: ident -> $narrow: ident, $nelem: ident;)*) => {
+            $(
+                #[inline]
+                pub fn $name(x: $wide) -> $narrow {
+                    let min = $wide::splat(::std::$nelem::MIN as $welem);
+                    let max = $wide::splat(::std::$nelem::MAX as $welem);
+                    let x = x.lt(min).select(min, x);
+                    let x = x.gt(max).select(max, x);
+                    unsafe {simd_cast(x)}
+                }
+                )*
+        }
+    }
+    clamps! {
+        u16x16_to_u8x16: u16x16, u16 -> u8x16, u8;
+        u32x8_to_u16x8: u32x8, u32 -> u16x8, u16;
+        u64x4_to_u32x4: u64x4, u64 -> u32x4, u32;
+        i64x4_to_i32x4: i64x4, i64 -> i32x4, i32;
+    }
+
+    #[cfg(all(any(target_arch = "x86",
+                  target_arch = "x86_64"),
+              target_feature = "sse2"))]
+    #[inline]
+    pub fn i32x8_to_i16x8(x: i32x8) -> i16x8 {
+        x.low().packs(x.high())
+    }
+    #[cfg(all(any(target_arch = "x86",
+                  target_arch = "x86_64"),
+              target_feature = "sse2"))]
+    #[inline]
+    pub fn i16x16_to_i8x16(x: i16x16) -> i8x16 {
+        x.low().packs(x.high())
+    }
+    #[cfg(not(all(any(target_arch = "x86",
+                      target_arch = "x86_64"),
+                  target_feature = "sse2")))]
+    clamps! {
+        i32x8_to_i16x8: i32x8, i32 -> i16x8, i16;
+        i16x16_to_i8x16: i16x16, i16 -> i8x16, i8;
+    }
+
+    // the `packs` versions on x86 against the portable ones
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        clamps! {
+            i32x8_generic: i32x8, i32 -> i16x8, i16;
+            i16x16_generic: i16x16, i16 -> i8x16, i8;
+        }
+
+        #[test]
+        fn i32x8_to_i16x8_matches_generic() {
+            let xs = mul_values!(i32);
+            for i in 0..xs.len() - 8 {
+                let x = i32x8::load(&xs, i);
+                assert!(i32x8_to_i16x8(x).eq(i32x8_generic(x)).all());
+            }
+        }
+        #[test]
+        fn i16x16_to_i8x16_matches_generic() {
+            let xs = mul_values!(i16);
+            for i in 0..xs.len() - 16 {
+                let x = i16x16::load(&xs, i);
+                assert!(i16x16_to_i8x16(x).eq(i16x16_generic(x)).all());
+            }
+        }
+    }
+}
+
+// Check the conversions against scalar `as`, with the saturation
+// spelled out, on edge cases including the limits of the narrow lane
+// type and one past them.
+#[cfg(test)]
+macro_rules! check_conversions {
+    ($narrow: ident, $nelem: ident, $wide: ident, $welem: ident, $half: ident, $length: expr) => {{
+        let (min, max) = ($nelem::min_value(), $nelem::max_value());
+        let mut ws = mul_values!($welem);
+        ws.extend(&[min as $welem, (min as $welem).wrapping_sub(1),
+                    max as $welem, (max as $welem).wrapping_add(1),
+                    $welem::min_value(), $welem::max_value()]);
+        let ns: Vec<$nelem> = ws.iter().map(|&x| x as $nelem).collect();
+        for i in 0..ns.len() - $length {
+            let x = $narrow::load(&ns, i);
+            let (w, (lo, hi)) = (x.widen(), x.widen_halves());
+            for k in 0..$length {
+                assert_eq!(w.extract(k), x.extract(k) as $welem);
+            }
+            for k in 0..$length / 2 {
+                assert_eq!(lo.extract(k), x.extract(k) as $welem);
+                assert_eq!(hi.extract(k), x.extract(k + $length / 2) as $welem);
+            }
+        }
+        for i in 0..ws.len() - $length {
+            let x = $wide::load(&ws, i);
+            let (t, s) = (x.narrow_truncate(), x.narrow_saturate());
+            for k in 0..$length {
+                let a = x.extract(k);
+                let want = if a < min as $welem {
+                    min
+                } else if a > max as $welem {
+                    max
+                } else {
+                    a as $nelem
+                };
+                assert_eq!(t.extract(k), a as $nelem, "{}", a);
+                assert_eq!(s.extract(k), want, "{}", a);
+            }
+        }
+    }}
+}
+
+#[cfg(test)]
+mod conversion_tests {
+    use super::*;
+    use std::{f32, f64};
+
+    #[test]
+    fn u8x16_u16x16() {
+        check_conversions!(u8x16, u8, u16x16, u16, u16x8, 16);
+    }
+    #[test]
+    fn i8x16_i16x16() {
+        check_conversions!(i8x16, i8, i16x16, i16, i16x8, 16);
+    }
+    #[test]
+    fn u16x8_u32x8() {
+        check_conversions!(u16x8, u16, u32x8, u32, u32x4, 8);
+    }
+    #[test]
+    fn i16x8_i32x8() {
+        check_conversions!(i16x8, i16, i32x8, i32, i32x4, 8);
+    }
+    #[test]
+    fn u32x4_u64x4() {
+        check_conversions!(u32x4, u32, u64x4, u64, u64x2, 4);
+    }
+    #[test]
+    fn i32x4_i64x4() {
+        check_conversions!(i32x4, i32, i64x4, i64, i64x2, 4);
+    }
+
+    #[test]
+    fn f32x4_widen() {
+        let xs = [0.0, -0.0, 1.1, -f32::MAX, f32::MIN_POSITIVE, 1e-45,
+                  f32::INFINITY, -f32::INFINITY, f32::NAN];
+        for i in 0..xs.len() - 4 {
+            let x = f32x4::load(&xs, i);
+            let (w, (lo, hi)) = (x.widen(), x.widen_halves());
+            for k in 0..4 {
+                let want = x.extract(k) as f64;
+                let got = w.extract(k);
+                assert!(got == want || (got.is_nan() && want.is_nan()), "{}", want);
+                assert_eq!(got.is_sign_negative(), want.is_sign_negative());
+            }
+            assert!((lo.eq(w.low()) | lo.ne(lo)).all());
+            assert!((hi.eq(w.high()) | hi.ne(hi)).all());
+        }
+    }
+    #[test]
+    fn f64x4_narrow() {
+        // rounding up to f32::MAX, overflow, underflow to zero and to
+        // a subnormal, and ties
+        let xs = [0.0, -0.0, 1.0 + 1.0 / (1u64 << 24) as f64, f32::MAX as f64,
+                  f32::MAX as f64 * (1.0 + 1e-9), 1e300, -1e300, 1e-50, 1e-40,
+                  f64::INFINITY, f64::NAN];
+        for i in 0..xs.len() - 4 {
+            let x = f64x4::load(&xs, i);
+            let n = x.narrow();
+            for k in 0..4 {
+                let want = x.extract(k) as f32;
+                let got = n.extract(k);
+                assert!(got == want || (got.is_nan() && want.is_nan()), "{}", x.extract(k));
+                assert_eq!(got.is_sign_negative(), want.is_sign_negative());
+            }
+        }
+    }
+}
@@ -3,6 +3,7 @@ use sixty_four::*;
 
 use super::super::bitcast;
 
+#[allow(deprecated)]
 pub use v256::{
     f64x4, bool64fx4, u64x4, i64x4, bool64ix4,
     f32x8, bool32fx8, u32x8, i32x8, bool32ix8,
@@ -51,6 +52,15 @@ extern "platform-intrinsic" {
     fn x86_mm256_testz_si256(x: u64x4, y: u64x4) -> i32;
 }
 
+// The variable blends aren't platform intrinsics.
+#[allow(improper_ctypes)]
+extern {
+    #[link_name = "llvm.x86.avx.blendv.ps.256"]
+    fn x86_mm256_blendv_ps(x: f32x8, y: f32x8, mask: f32x8) -> f32x8;
+    #[link_name = "llvm.x86.avx.blendv.pd.256"]
+    fn x86_mm256_blendv_pd(x: f64x4, y: f64x4, mask: f64x4) -> f64x4;
+}
+
 #[doc(hidden)]
 pub mod common {
     use super::*;
@@ -76,13 +86,86 @@ pub mod common {
     }
 
     bools! {
-        bool32fx8, bool32fx8_all, bool32fx8_any, x86_mm256_testc_ps, x86_mm256_testz_ps;
-        bool64fx4, bool64fx4_all, bool64fx4_any, x86_mm256_testc_pd, x86_mm256_testz_pd;
         bool8ix32, bool8ix32_all, bool8ix32_any, x86_mm256_testc_si256, x86_mm256_testz_si256;
         bool16ix16, bool16ix16_all, bool16ix16_any, x86_mm256_testc_si256, x86_mm256_testz_si256;
         bool32ix8, bool32ix8_all, bool32ix8_any, x86_mm256_testc_si256, x86_mm256_testz_si256;
         bool64ix4, bool64ix4_all, bool64ix4_any, x86_mm256_testc_si256, x86_mm256_testz_si256;
     }
+
+    // `vminps` and friends disagree with `min_num` and `max_num`, as
+    // the SSE versions do.
+    #[inline]
+    pub fn f32x8_min_num(x: f32x8, y: f32x8) -> f32x8 {
+        min_num_lanes!(x, y)
+    }
+    #[inline]
+    pub fn f32x8_max_num(x: f32x8, y: f32x8) -> f32x8 {
+        max_num_lanes!(x, y)
+    }
+    #[inline]
+    pub fn f64x4_min_num(x: f64x4, y: f64x4) -> f64x4 {
+        min_num_lanes!(x, y)
+    }
+    #[inline]
+    pub fn f64x4_max_num(x: f64x4, y: f64x4) -> f64x4 {
+        max_num_lanes!(x, y)
+    }
+
+    #[inline]
+    pub fn bool32ix8_bitmask(x: bool32ix8) -> u64 {
+        unsafe {super::x86_mm256_movemask_ps(mem::transmute(x)) as u64}
+    }
+    #[inline]
+    pub fn bool64ix4_bitmask(x: bool64ix4) -> u64 {
+        unsafe {super::x86_mm256_movemask_pd(mem::transmute(x)) as u64}
+    }
+    #[inline]
+    pub fn bool32ix8_select(mask: bool32ix8, then: i32x8, else_: i32x8) -> i32x8 {
+        unsafe {
+            mem::transmute(super::x86_mm256_blendv_ps(mem::transmute(else_),
+                                                      mem::transmute(then),
+                                                      mem::transmute(mask)))
+        }
+    }
+    #[inline]
+    pub fn bool64ix4_select(mask: bool64ix4, then: i64x4, else_: i64x4) -> i64x4 {
+        unsafe {
+            mem::transmute(super::x86_mm256_blendv_pd(mem::transmute(else_),
+                                                      mem::transmute(then),
+                                                      mem::transmute(mask)))
+        }
+    }
+    #[cfg(target_feature = "avx2")]
+    pub use x86::avx2::common::{bool16ix16_select, bool8ix32_select};
+    #[cfg(not(target_feature = "avx2"))]
+    bitwise_selects! {
+        bool16ix16: bool16ix16_select(i16x16);
+        bool8ix32: bool8ix32_select(i8x32);
+    }
+
+    #[cfg(target_feature = "avx2")]
+    pub use x86::avx2::common::{u64x4_swap_bytes, u32x8_swap_bytes, u16x16_swap_bytes,
+                                u8x32_reverse_bits};
+    #[cfg(not(target_feature = "avx2"))]
+    use {simd_shuffle4, simd_shuffle8, simd_shuffle16, simd_shuffle32};
+    #[cfg(not(target_feature = "avx2"))]
+    half_bit_swaps! {
+        u64x4_swap_bytes(u64x4): swap_bytes, simd_shuffle4 [0, 1, 2, 3];
+        u32x8_swap_bytes(u32x8): swap_bytes, simd_shuffle8 [0, 1, 2, 3, 4, 5, 6, 7];
+        u16x16_swap_bytes(u16x16): swap_bytes, simd_shuffle16 [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+        u8x32_reverse_bits(u8x32): reverse_bits, simd_shuffle32 [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
+                                                                 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31];
+    }
+
+    // there's no 256-bit `movemask_epi8` before AVX2
+    #[inline]
+    pub fn bool16ix16_bitmask(x: bool16ix16) -> u64 {
+        x.low().to_bitmask() | x.high().to_bitmask() << 8
+    }
+    #[inline]
+    pub fn bool8ix32_bitmask(x: bool8ix32) -> u64 {
+        x.low().to_bitmask() | x.high().to_bitmask() << 16
+    }
 }
 
 // 128-bit vectors:
@@ -148,7 +231,7 @@ impl AvxF64x4 for f64x4 {
 pub trait AvxBool64fx4 {
     fn move_mask(self) -> u32;
 }
-impl AvxBool64fx4 for bool64fx4 {
+impl AvxBool64fx4 for bool64ix4 {
     #[inline]
     fn move_mask(self) -> u32 {
         unsafe { x86_mm256_movemask_pd(bitcast(self)) as u32 }
@@ -225,7 +308,7 @@ impl AvxF32x8 for f32x8 {
 pub trait AvxBool32fx8 {
     fn move_mask(self) -> u32;
 }
-impl AvxBool32fx8 for bool32fx8 {
+impl AvxBool32fx8 for bool32ix8 {
     #[inline]
     fn move_mask(self) -> u32 {
         unsafe { x86_mm256_movemask_ps(bitcast(self)) as u32 }
@@ -233,7 +316,7 @@ impl AvxBool32fx8 for bool32fx8 {
 }
 
 pub trait AvxBool32fx4 {}
-impl AvxBool32fx4 for bool32fx4 {}
+impl AvxBool32fx4 for bool32ix4 {}
 
 // 64 bit floats
 
@@ -247,7 +330,7 @@ impl AvxF64x2 for f64x2 {
 }
 
 pub trait AvxBool64fx2 {}
-impl AvxBool64fx2 for bool64fx2 {}
+impl AvxBool64fx2 for bool64ix2 {}
 
 // 64 bit integers
 
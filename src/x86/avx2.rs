@@ -53,6 +53,80 @@ extern "platform-intrinsic" {
     fn x86_mm256_subs_epu16(x: u16x16, y: u16x16) -> u16x16;
 }
 
+// The variable blends aren't platform intrinsics.
+#[allow(improper_ctypes)]
+extern {
+    #[link_name = "llvm.x86.avx2.pblendvb"]
+    fn x86_mm256_blendv_epi8(x: i8x32, y: i8x32, mask: i8x32) -> i8x32;
+}
+
+#[doc(hidden)]
+pub mod common {
+    use super::*;
+    use std::mem;
+
+    #[inline]
+    pub fn bool8ix32_select(mask: bool8ix32, then: i8x32, else_: i8x32) -> i8x32 {
+        unsafe {super::x86_mm256_blendv_epi8(else_, then, mem::transmute(mask))}
+    }
+    // each lane of a mask is all ones or all zeros, so a blend on
+    // bytes works for wider lanes.
+    #[inline]
+    pub fn bool16ix16_select(mask: bool16ix16, then: i16x16, else_: i16x16) -> i16x16 {
+        unsafe {
+            mem::transmute(super::x86_mm256_blendv_epi8(mem::transmute(else_),
+                                                        mem::transmute(then),
+                                                        mem::transmute(mask)))
+        }
+    }
+
+    // `pshufb` shuffles within each 128-bit half, so the indices
+    // repeat.
+    macro_rules! byte_shuffles {
+        ($($func: ident ($ty: ident) [$($idx: expr),*];)*) => {
+            $(
+                #[inline]
+                pub fn $func(x: $ty) -> $ty {
+                    unsafe {
+                        mem::transmute(super::x86_mm256_shuffle_epi8(mem::transmute(x),
+                                                                     i8x32::new($($idx),*)))
+                    }
+                }
+                )*
+        }
+    }
+
+    byte_shuffles! {
+        u16x16_swap_bytes(u16x16) [1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14,
+                                   1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14];
+        u32x8_swap_bytes(u32x8) [3, 2, 1, 0, 7, 6, 5, 4, 11, 10, 9, 8, 15, 14, 13, 12,
+                                 3, 2, 1, 0, 7, 6, 5, 4, 11, 10, 9, 8, 15, 14, 13, 12];
+        u64x4_swap_bytes(u64x4) [7, 6, 5, 4, 3, 2, 1, 0, 15, 14, 13, 12, 11, 10, 9, 8,
+                                 7, 6, 5, 4, 3, 2, 1, 0, 15, 14, 13, 12, 11, 10, 9, 8];
+    }
+
+    // look up the reversal of each nibble, like the SSSE3 version.
+    #[inline]
+    pub fn u8x32_reverse_bits(x: u8x32) -> u8x32 {
+        let low = u8x32::new(0x00, 0x80, 0x40, 0xc0, 0x20, 0xa0, 0x60, 0xe0,
+                             0x10, 0x90, 0x50, 0xd0, 0x30, 0xb0, 0x70, 0xf0,
+                             0x00, 0x80, 0x40, 0xc0, 0x20, 0xa0, 0x60, 0xe0,
+                             0x10, 0x90, 0x50, 0xd0, 0x30, 0xb0, 0x70, 0xf0);
+        let high = u8x32::new(0x00, 0x08, 0x04, 0x0c, 0x02, 0x0a, 0x06, 0x0e,
+                              0x01, 0x09, 0x05, 0x0d, 0x03, 0x0b, 0x07, 0x0f,
+                              0x00, 0x08, 0x04, 0x0c, 0x02, 0x0a, 0x06, 0x0e,
+                              0x01, 0x09, 0x05, 0x0d, 0x03, 0x0b, 0x07, 0x0f);
+        let nibble = u8x32::splat(0x0f);
+        unsafe {
+            let low: u8x32 = mem::transmute(super::x86_mm256_shuffle_epi8(mem::transmute(low),
+                                                                          mem::transmute(x & nibble)));
+            let high: u8x32 = mem::transmute(super::x86_mm256_shuffle_epi8(mem::transmute(high),
+                                                                           mem::transmute((x >> 4u32) & nibble)));
+            low | high
+        }
+    }
+}
+
 // broken on rustc 1.7.0-nightly (1ddaf8bdf 2015-12-12)
 // pub trait Avx2F32x8 {
 //     fn permutevar(self, other: i32x8) -> f32x8;
@@ -0,0 +1,142 @@
+use super::super::*;
+use sixty_four::*;
+
+pub use v256::{f64x4, f32x8};
+
+#[allow(dead_code)]
+extern "platform-intrinsic" {
+    fn x86_mm_fmadd_ps(x: f32x4, y: f32x4, z: f32x4) -> f32x4;
+    fn x86_mm_fmadd_pd(x: f64x2, y: f64x2, z: f64x2) -> f64x2;
+    fn x86_mm256_fmadd_ps(x: f32x8, y: f32x8, z: f32x8) -> f32x8;
+    fn x86_mm256_fmadd_pd(x: f64x4, y: f64x4, z: f64x4) -> f64x4;
+    fn x86_mm_fmaddsub_ps(x: f32x4, y: f32x4, z: f32x4) -> f32x4;
+    fn x86_mm_fmaddsub_pd(x: f64x2, y: f64x2, z: f64x2) -> f64x2;
+    fn x86_mm256_fmaddsub_ps(x: f32x8, y: f32x8, z: f32x8) -> f32x8;
+    fn x86_mm256_fmaddsub_pd(x: f64x4, y: f64x4, z: f64x4) -> f64x4;
+    fn x86_mm_fmsub_ps(x: f32x4, y: f32x4, z: f32x4) -> f32x4;
+    fn x86_mm_fmsub_pd(x: f64x2, y: f64x2, z: f64x2) -> f64x2;
+    fn x86_mm256_fmsub_ps(x: f32x8, y: f32x8, z: f32x8) -> f32x8;
+    fn x86_mm256_fmsub_pd(x: f64x4, y: f64x4, z: f64x4) -> f64x4;
+    fn x86_mm_fmsubadd_ps(x: f32x4, y: f32x4, z: f32x4) -> f32x4;
+    fn x86_mm_fmsubadd_pd(x: f64x2, y: f64x2, z: f64x2) -> f64x2;
+    fn x86_mm256_fmsubadd_ps(x: f32x8, y: f32x8, z: f32x8) -> f32x8;
+    fn x86_mm256_fmsubadd_pd(x: f64x4, y: f64x4, z: f64x4) -> f64x4;
+    fn x86_mm_fnmadd_ps(x: f32x4, y: f32x4, z: f32x4) -> f32x4;
+    fn x86_mm_fnmadd_pd(x: f64x2, y: f64x2, z: f64x2) -> f64x2;
+    fn x86_mm256_fnmadd_ps(x: f32x8, y: f32x8, z: f32x8) -> f32x8;
+    fn x86_mm256_fnmadd_pd(x: f64x4, y: f64x4, z: f64x4) -> f64x4;
+    fn x86_mm_fnmsub_ps(x: f32x4, y: f32x4, z: f32x4) -> f32x4;
+    fn x86_mm_fnmsub_pd(x: f64x2, y: f64x2, z: f64x2) -> f64x2;
+    fn x86_mm256_fnmsub_ps(x: f32x8, y: f32x8, z: f32x8) -> f32x8;
+    fn x86_mm256_fnmsub_pd(x: f64x4, y: f64x4, z: f64x4) -> f64x4;
+}
+
+#[doc(hidden)]
+pub mod common {
+    use super::*;
+
+    macro_rules! fmas {
+        ($($ty: ident: $mul_add: ident ($fmadd: ident),
+           $mul_sub: ident ($fmsub: ident),
+           $neg_mul_add: ident ($fnmadd: ident);)*) => {
+            $(
+                #[inline]
+                pub fn $mul_add(x: $ty, y: $ty, z: $ty) -> $ty {
+                    unsafe {super::$fmadd(x, y, z)}
+                }
+                #[inline]
+                pub fn $mul_sub(x: $ty, y: $ty, z: $ty) -> $ty {
+                    unsafe {super::$fmsub(x, y, z)}
+                }
+                #[inline]
+                pub fn $neg_mul_add(x: $ty, y: $ty, z: $ty) -> $ty {
+                    unsafe {super::$fnmadd(x, y, z)}
+                }
+                )*
+        }
+    }
+
+    fmas! {
+        f32x4: f32x4_mul_add(x86_mm_fmadd_ps), f32x4_mul_sub(x86_mm_fmsub_ps),
+            f32x4_neg_mul_add(x86_mm_fnmadd_ps);
+        f64x2: f64x2_mul_add(x86_mm_fmadd_pd), f64x2_mul_sub(x86_mm_fmsub_pd),
+            f64x2_neg_mul_add(x86_mm_fnmadd_pd);
+        f32x8: f32x8_mul_add(x86_mm256_fmadd_ps), f32x8_mul_sub(x86_mm256_fmsub_ps),
+            f32x8_neg_mul_add(x86_mm256_fnmadd_ps);
+        f64x4: f64x4_mul_add(x86_mm256_fmadd_pd), f64x4_mul_sub(x86_mm256_fmsub_pd),
+            f64x4_neg_mul_add(x86_mm256_fnmadd_pd);
+    }
+}
+
+macro_rules! fma_traits {
+    ($($trayt: ident for $ty: ident:
+       $fmadd: ident, $fmaddsub: ident, $fmsub: ident,
+       $fmsubadd: ident, $fnmadd: ident, $fnmsub: ident;)*) => {
+        $(
+            pub trait $trayt {
+                /// Compute `self * b + c` with a single rounding.
+                fn fmadd(self, b: Self, c: Self) -> Self;
+                /// Compute `self * b - c` in the even lanes and `self
+                /// * b + c` in the odd lanes, with a single rounding.
+                fn fmaddsub(self, b: Self, c: Self) -> Self;
+                /// Compute `self * b - c` with a single rounding.
+                fn fmsub(self, b: Self, c: Self) -> Self;
+                /// Compute `self * b + c` in the even lanes and `self
+                /// * b - c` in the odd lanes, with a single rounding.
+                fn fmsubadd(self, b: Self, c: Self) -> Self;
+                /// Compute `-(self * b) + c` with a single rounding.
+                fn fnmadd(self, b: Self, c: Self) -> Self;
+                /// Compute `-(self * b) - c` with a single rounding.
+                fn fnmsub(self, b: Self, c: Self) -> Self;
+            }
+            impl $trayt for $ty {
+                #[inline]
+                fn fmadd(self, b: Self, c: Self) -> Self {
+                    unsafe { $fmadd(self, b, c) }
+                }
+                #[inline]
+                fn fmaddsub(self, b: Self, c: Self) -> Self {
+                    unsafe { $fmaddsub(self, b, c) }
+                }
+                #[inline]
+                fn fmsub(self, b: Self, c: Self) -> Self {
+                    unsafe { $fmsub(self, b, c) }
+                }
+                #[inline]
+                fn fmsubadd(self, b: Self, c: Self) -> Self {
+                    unsafe { $fmsubadd(self, b, c) }
+                }
+                #[inline]
+                fn fnmadd(self, b: Self, c: Self) -> Self {
+                    unsafe { $fnmadd(self, b, c) }
+                }
+                #[inline]
+                fn fnmsub(self, b: Self, c: Self) -> Self {
+                    unsafe { $fnmsub(self, b, c) }
+                }
+            }
+            )*
+    }
+}
+
+// 32 bit floats
+
+fma_traits! {
+    FmaF32x4 for f32x4:
+        x86_mm_fmadd_ps, x86_mm_fmaddsub_ps, x86_mm_fmsub_ps,
+        x86_mm_fmsubadd_ps, x86_mm_fnmadd_ps, x86_mm_fnmsub_ps;
+    FmaF32x8 for f32x8:
+        x86_mm256_fmadd_ps, x86_mm256_fmaddsub_ps, x86_mm256_fmsub_ps,
+        x86_mm256_fmsubadd_ps, x86_mm256_fnmadd_ps, x86_mm256_fnmsub_ps;
+}
+
+// 64 bit floats
+
+fma_traits! {
+    FmaF64x2 for f64x2:
+        x86_mm_fmadd_pd, x86_mm_fmaddsub_pd, x86_mm_fmsub_pd,
+        x86_mm_fmsubadd_pd, x86_mm_fnmadd_pd, x86_mm_fnmsub_pd;
+    FmaF64x4 for f64x4:
+        x86_mm256_fmadd_pd, x86_mm256_fmaddsub_pd, x86_mm256_fmsub_pd,
+        x86_mm256_fmsubadd_pd, x86_mm256_fnmadd_pd, x86_mm256_fnmsub_pd;
+}
@@ -1,7 +1,8 @@
 use super::super::*;
 use {bitcast, simd_cast, f32x2};
 
-pub use sixty_four::{f64x2, i64x2, u64x2, bool64ix2, bool64fx2};
+#[allow(deprecated)]
+pub use sixty_four::{f64x2, i64x2, u64x2, bool64ix2, bool64fx2, bool64i, bool64f};
 
 //pub use super::{u64x2, i64x2, f64x2, bool64ix2, bool64fx2};
 
@@ -48,8 +49,13 @@ extern "platform-intrinsic" {
 #[doc(hidden)]
 pub mod common {
     use super::super::super::*;
+    use super::f64x2;
     use std::mem;
 
+    // `rcpps` and `rsqrtps` have a relative error of at most
+    // 1.5 * 2^-12.
+    pub const F32X4_APPROX_BITS: u32 = 11;
+
     #[inline]
     pub fn f32x4_sqrt(x: f32x4) -> f32x4 {
         unsafe {super::x86_mm_sqrt_ps(x)}
@@ -62,13 +68,23 @@ pub mod common {
     pub fn f32x4_approx_reciprocal(x: f32x4) -> f32x4 {
         unsafe {super::x86_mm_rcp_ps(x)}
     }
+    // `minps` and `maxps` return their second operand if either is
+    // NaN, and don't order signed zeros.
+    #[inline]
+    pub fn f32x4_min_num(x: f32x4, y: f32x4) -> f32x4 {
+        min_num_lanes!(x, y)
+    }
+    #[inline]
+    pub fn f32x4_max_num(x: f32x4, y: f32x4) -> f32x4 {
+        max_num_lanes!(x, y)
+    }
     #[inline]
-    pub fn f32x4_max(x: f32x4, y: f32x4) -> f32x4 {
-        unsafe {super::x86_mm_max_ps(x, y)}
+    pub fn f64x2_min_num(x: f64x2, y: f64x2) -> f64x2 {
+        min_num_lanes!(x, y)
     }
     #[inline]
-    pub fn f32x4_min(x: f32x4, y: f32x4) -> f32x4 {
-        unsafe {super::x86_mm_min_ps(x, y)}
+    pub fn f64x2_max_num(x: f64x2, y: f64x2) -> f64x2 {
+        max_num_lanes!(x, y)
     }
 
     macro_rules! bools {
@@ -91,16 +107,55 @@ pub mod common {
     }
 
     bools! {
-        bool32fx4, bool32fx4_all, bool32fx4_any, x86_mm_movemask_ps, 4;
         bool8ix16, bool8ix16_all, bool8ix16_any, x86_mm_movemask_epi8, 16;
         bool16ix8, bool16ix8_all, bool16ix8_any, x86_mm_movemask_epi8, 16;
-        bool32ix4, bool32ix4_all, bool32ix4_any, x86_mm_movemask_epi8, 16;
+        bool32ix4, bool32ix4_all, bool32ix4_any, x86_mm_movemask_ps, 4;
+    }
+
+    #[inline]
+    pub fn bool32ix4_bitmask(x: bool32ix4) -> u64 {
+        unsafe {super::x86_mm_movemask_ps(mem::transmute(x)) as u64}
+    }
+    // `movemask_epi8` gives two bits per 16-bit lane, so narrow to
+    // bytes first.
+    #[inline]
+    pub fn bool16ix8_bitmask(x: bool16ix8) -> u64 {
+        unsafe {
+            let x: i16x8 = mem::transmute(x);
+            (super::x86_mm_movemask_epi8(super::x86_mm_packs_epi16(x, x)) & 0xff) as u64
+        }
+    }
+    #[inline]
+    pub fn bool8ix16_bitmask(x: bool8ix16) -> u64 {
+        unsafe {super::x86_mm_movemask_epi8(mem::transmute(x)) as u64}
+    }
+
+    #[cfg(target_feature = "sse4.1")]
+    pub use x86::sse4_1::common::{bool8ix16_select, bool16ix8_select, bool32ix4_select};
+    #[cfg(not(target_feature = "sse4.1"))]
+    bitwise_selects! {
+        bool8ix16: bool8ix16_select(i8x16);
+        bool16ix8: bool16ix8_select(i16x8);
+        bool32ix4: bool32ix4_select(i32x4);
+    }
+
+    #[cfg(target_feature = "ssse3")]
+    pub use x86::ssse3::common::{u16x8_swap_bytes, u32x4_swap_bytes, u8x16_reverse_bits};
+    #[cfg(not(target_feature = "ssse3"))]
+    bit_group_swaps! {
+        u16x8_swap_bytes(u16x8) [8 => 0x00ff];
+        u32x4_swap_bytes(u32x4) [8 => 0x00ff00ff, 16 => 0x0000ffff];
+        u8x16_reverse_bits(u8x16) [1 => 0x55, 2 => 0x33, 4 => 0x0f];
     }
 }
 
 // 32 bit floats
 
 pub trait Sse2F32x4 {
+    /// Convert the low two lanes to 64-bit floats.
+    ///
+    /// The high two lanes are dropped; use `f32x4::widen` to convert
+    /// all four.
     fn to_f64(self) -> f64x2;
     fn move_mask(self) -> u32;
 }
@@ -118,7 +173,7 @@ impl Sse2F32x4 for f32x4 {
 pub trait Sse2Bool32fx4 {
     fn move_mask(self) -> u32;
 }
-impl Sse2Bool32fx4 for bool32fx4 {
+impl Sse2Bool32fx4 for bool32ix4 {
     #[inline]
     fn move_mask(self) -> u32 {
         unsafe { x86_mm_movemask_ps(bitcast(self)) as u32}
@@ -157,7 +212,7 @@ impl Sse2F64x2 for f64x2 {
 pub trait Sse2Bool64fx2 {
     fn move_mask(self) -> u32;
 }
-impl Sse2Bool64fx2 for bool64fx2 {
+impl Sse2Bool64fx2 for bool64ix2 {
     #[inline]
     fn move_mask(self) -> u32 {
         unsafe { x86_mm_movemask_pd(bitcast(self)) as u32}
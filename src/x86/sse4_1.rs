@@ -22,6 +22,47 @@ extern "platform-intrinsic" {
     fn x86_mm_testz_si128(x: u64x2, y: u64x2) -> i32;
 }
 
+// The variable blends aren't platform intrinsics.
+#[allow(improper_ctypes)]
+extern {
+    #[link_name = "llvm.x86.sse41.pblendvb"]
+    fn x86_mm_blendv_epi8(x: i8x16, y: i8x16, mask: i8x16) -> i8x16;
+    #[link_name = "llvm.x86.sse41.blendvps"]
+    fn x86_mm_blendv_ps(x: f32x4, y: f32x4, mask: f32x4) -> f32x4;
+    #[link_name = "llvm.x86.sse41.blendvpd"]
+    fn x86_mm_blendv_pd(x: f64x2, y: f64x2, mask: f64x2) -> f64x2;
+}
+
+#[doc(hidden)]
+pub mod common {
+    use super::*;
+    use std::mem;
+
+    // each lane of a mask is all ones or all zeros, so a blend on
+    // bytes works for any lane width.
+    macro_rules! selects {
+        ($($name: ident: $select: ident ($repr: ty) = $blendv: ident;)*) => {
+            $(
+                #[inline]
+                pub fn $select(mask: $name, then: $repr, else_: $repr) -> $repr {
+                    unsafe {
+                        mem::transmute(super::$blendv(mem::transmute(else_),
+                                                      mem::transmute(then),
+                                                      mem::transmute(mask)))
+                    }
+                }
+                )*
+        }
+    }
+
+    selects! {
+        bool8ix16: bool8ix16_select(i8x16) = x86_mm_blendv_epi8;
+        bool16ix8: bool16ix8_select(i16x8) = x86_mm_blendv_epi8;
+        bool32ix4: bool32ix4_select(i32x4) = x86_mm_blendv_ps;
+        bool64ix2: bool64ix2_select(i64x2) = x86_mm_blendv_pd;
+    }
+}
+
 // 32 bit floats
 
 pub trait Sse41F32x4 {}
@@ -25,6 +25,45 @@ extern "platform-intrinsic" {
     fn x86_mm_sign_epi32(x: i32x4, y: i32x4) -> i32x4;
 }
 
+#[doc(hidden)]
+pub mod common {
+    use super::*;
+    use sixty_four::u64x2;
+    use std::mem;
+
+    macro_rules! byte_shuffles {
+        ($($func: ident ($ty: ident) [$($idx: expr),*];)*) => {
+            $(
+                #[inline]
+                pub fn $func(x: $ty) -> $ty {
+                    unsafe {
+                        mem::transmute(super::x86_mm_shuffle_epi8(mem::transmute(x),
+                                                                  i8x16::new($($idx),*)))
+                    }
+                }
+                )*
+        }
+    }
+
+    byte_shuffles! {
+        u16x8_swap_bytes(u16x8) [1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14];
+        u32x4_swap_bytes(u32x4) [3, 2, 1, 0, 7, 6, 5, 4, 11, 10, 9, 8, 15, 14, 13, 12];
+        u64x2_swap_bytes(u64x2) [7, 6, 5, 4, 3, 2, 1, 0, 15, 14, 13, 12, 11, 10, 9, 8];
+    }
+
+    // look up the reversal of each nibble, with the low nibble
+    // moving to the high one and vice versa.
+    #[inline]
+    pub fn u8x16_reverse_bits(x: u8x16) -> u8x16 {
+        let low = u8x16::new(0x00, 0x80, 0x40, 0xc0, 0x20, 0xa0, 0x60, 0xe0,
+                             0x10, 0x90, 0x50, 0xd0, 0x30, 0xb0, 0x70, 0xf0);
+        let high = u8x16::new(0x00, 0x08, 0x04, 0x0c, 0x02, 0x0a, 0x06, 0x0e,
+                              0x01, 0x09, 0x05, 0x0d, 0x03, 0x0b, 0x07, 0x0f);
+        let nibble = u8x16::splat(0x0f);
+        low.shuffle_bytes(x & nibble) | high.shuffle_bytes((x >> 4u32) & nibble)
+    }
+}
+
 // 32 bit integers
 
 pub trait Ssse3I32x4 {
@@ -0,0 +1,101 @@
+//! Check that `blend_bitmask` with a constant mask compiles to an
+//! immediate blend, by disassembling this test binary with `objdump`:
+//!
+//! ```text
+//! RUSTFLAGS="-C target-feature=+avx" cargo test --test blend_asm
+//! ```
+//!
+//! The mask only folds to an immediate with optimizations, which is
+//! why `Cargo.toml` sets `opt-level` for the test profile. The
+//! disassembly tests only exist when the immediate blends are
+//! available (SSE4.1 and AVX), and fail if `objdump` can't be run or
+//! doesn't find the function.
+#![feature(cfg_target_feature)]
+
+extern crate simd;
+
+use simd::{f32x4, i16x8};
+#[cfg(target_feature = "avx")]
+use simd::x86::avx::f32x8;
+
+#[no_mangle]
+#[inline(never)]
+pub fn blend_f32x4_0101(a: f32x4, b: f32x4) -> f32x4 {
+    a.blend_bitmask(b, 0b0101)
+}
+#[no_mangle]
+#[inline(never)]
+pub fn blend_i16x8_00110101(a: i16x8, b: i16x8) -> i16x8 {
+    a.blend_bitmask(b, 0b00110101)
+}
+#[cfg(target_feature = "avx")]
+#[no_mangle]
+#[inline(never)]
+pub fn blend_f32x8_01100101(a: f32x8, b: f32x8) -> f32x8 {
+    a.blend_bitmask(b, 0b01100101)
+}
+
+/// The disassembled instructions of the function `name` in this
+/// binary.
+#[cfg(target_feature = "sse4.1")]
+fn disassemble(name: &str) -> Vec<String> {
+    use std::env;
+    use std::process::Command;
+
+    let exe = env::current_exe().unwrap();
+    let output = Command::new("objdump").arg("-d").arg("--no-show-raw-insn").arg(&exe)
+        .output()
+        .unwrap_or_else(|e| panic!("couldn't run objdump, which this test needs: {}", e));
+    assert!(output.status.success(), "objdump failed: {}",
+            String::from_utf8_lossy(&output.stderr));
+    let text = String::from_utf8_lossy(&output.stdout);
+    let header = format!("<{}>:", name);
+    let mut lines = text.lines().skip_while(|line| !line.ends_with(&header));
+    assert!(lines.next().is_some(), "objdump didn't find {} in {}", name, exe.display());
+    lines.take_while(|line| !line.trim().is_empty()).map(|line| line.to_string()).collect()
+}
+
+#[cfg(target_feature = "sse4.1")]
+fn assert_instr(name: &str, instr: &str) {
+    let body = disassemble(name);
+    assert!(body.iter().any(|line| line.contains(instr)),
+            "{} doesn't use `{}`:\n{}", name, instr, body.join("\n"));
+    assert!(!body.iter().any(|line| line.contains("blendv")),
+            "{} uses a variable blend:\n{}", name, body.join("\n"));
+}
+
+#[test]
+fn f32x4() {
+    let r = blend_f32x4_0101(f32x4::new(0.0, 1.0, 2.0, 3.0), f32x4::new(4.0, 5.0, 6.0, 7.0));
+    assert!(r.eq(f32x4::new(4.0, 1.0, 6.0, 3.0)).all());
+}
+#[cfg(target_feature = "sse4.1")]
+#[test]
+fn f32x4_asm() {
+    assert_instr("blend_f32x4_0101", "blendps");
+}
+
+#[test]
+fn i16x8() {
+    let r = blend_i16x8_00110101(i16x8::new(0, 1, 2, 3, 4, 5, 6, 7),
+                                 i16x8::new(8, 9, 10, 11, 12, 13, 14, 15));
+    assert!(r.eq(i16x8::new(8, 1, 10, 3, 12, 13, 6, 7)).all());
+}
+#[cfg(target_feature = "sse4.1")]
+#[test]
+fn i16x8_asm() {
+    assert_instr("blend_i16x8_00110101", "pblendw");
+}
+
+#[cfg(target_feature = "avx")]
+#[test]
+fn f32x8() {
+    let r = blend_f32x8_01100101(f32x8::new(0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0),
+                                 f32x8::new(8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0));
+    assert!(r.eq(f32x8::new(8.0, 1.0, 10.0, 3.0, 4.0, 13.0, 14.0, 7.0)).all());
+}
+#[cfg(target_feature = "avx")]
+#[test]
+fn f32x8_asm() {
+    assert_instr("blend_f32x8_01100101", "vblendps");
+}